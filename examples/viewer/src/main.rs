@@ -5,7 +5,7 @@ use vello::{
     kurbo::{Affine, Rect, Vec2},
     peniko::{Color, Fill},
     util::{RenderContext, RenderSurface},
-    Renderer, RendererOptions, Scene, SceneBuilder,
+    Renderer, RendererOptions, Scene, SceneBuilder, SceneFragment,
 };
 use winit::{
     dpi::LogicalSize,
@@ -30,6 +30,9 @@ fn main() {
     let event_loop = EventLoop::new();
     let mut cached_window: Option<Window> = None;
     let mut renderer: Option<Renderer> = None;
+    let mut rive_renderer = rive_rs::Renderer::default();
+    // Reused across frames so that its encoding buffers are only allocated once.
+    let mut fragment = SceneFragment::default();
     let mut render_cx = RenderContext::new().unwrap();
     let mut render_state: Option<RenderState> = None;
 
@@ -67,7 +70,7 @@ fn main() {
                             ElementState::Released => {
                                 scene.pointer_up(mouse_pos.x as f32, mouse_pos.y as f32, &viewport)
                             }
-                        }
+                        };
                     }
                 }
                 WindowEvent::CursorMoved { position, .. } => {
@@ -76,6 +79,11 @@ fn main() {
                         scene.pointer_move(mouse_pos.x as f32, mouse_pos.y as f32, &viewport);
                     }
                 }
+                WindowEvent::CursorLeft { .. } => {
+                    if let Some(scene) = &mut scene {
+                        scene.pointer_exit(mouse_pos.x as f32, mouse_pos.y as f32, &viewport);
+                    }
+                }
                 WindowEvent::MouseWheel { delta, .. } => match delta {
                     winit::event::MouseScrollDelta::LineDelta(_, lines_y) => {
                         scroll_delta = (scroll_delta
@@ -103,7 +111,7 @@ fn main() {
             }
         }
         Event::RedrawRequested(_) => {
-            let mut rive_renderer = rive_rs::Renderer::default();
+            rive_renderer.reset();
             let factor = (scroll_delta / SCROLL_FACTOR_THRESHOLD).max(1.0) as u32;
 
             let elapsed = &frame_start_time.elapsed();
@@ -151,9 +159,12 @@ fn main() {
             if let Some(scene) = &mut scene {
                 scene.advance_and_maybe_draw(&mut rive_renderer, *elapsed, &mut viewport);
 
+                // Encode the frame once and append the encoding for every copy.
+                rive_renderer.append_to(&mut SceneBuilder::for_fragment(&mut fragment), None);
+
                 for i in 0..factor.pow(2) {
                     builder.append(
-                        rive_renderer.scene(),
+                        &fragment,
                         Some(
                             Affine::default()
                                 .then_scale(1.0 / factor as f64)
@@ -0,0 +1,21 @@
+use core::marker::PhantomData;
+
+use crate::ffi;
+
+use super::{component_property, try_from_component};
+
+/// A bone of a skeleton. Root bones are bones as well.
+pub struct Bone<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Bone<'_> {
+    component_property!(length, set_length, f32, Length);
+    // Rotation in radians.
+    component_property!(rotation, set_rotation, f32, Rotation);
+    component_property!(scale_x, set_scale_x, f32, ScaleX);
+    component_property!(scale_y, set_scale_y, f32, ScaleY);
+}
+
+try_from_component!(Bone, Bone);
@@ -0,0 +1,30 @@
+use alloc::{collections::BTreeMap, string::String};
+use core::marker::PhantomData;
+
+use crate::{ffi, state_machine::Property};
+
+use super::try_from_component;
+
+/// The definition of an event that state machines can report, as opposed to
+/// [`state_machine::Event`](crate::state_machine::Event) which is a reported occurrence of it.
+pub struct Event<'a> {
+    raw_event: *mut ffi::Event,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Event<'_> {
+    pub fn properties(&self) -> BTreeMap<String, Property> {
+        let mut properties = BTreeMap::new();
+
+        unsafe {
+            ffi::rive_rs_event_properties(
+                self.raw_event,
+                &mut properties as *mut BTreeMap<String, Property>,
+            );
+        }
+
+        properties
+    }
+}
+
+try_from_component!(Event, raw_event, Event);
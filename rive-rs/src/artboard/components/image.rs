@@ -0,0 +1,27 @@
+use core::marker::PhantomData;
+
+use crate::{ffi, renderer::BlendMode};
+
+use super::{blend_mode_from_u32, component_property, try_from_component, Node};
+
+/// A drawable showing an image asset.
+pub struct Image<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Image<'_> {
+    #[inline]
+    pub fn node(&mut self) -> Node {
+        Node::new(self.raw_component)
+    }
+
+    component_property!(
+        blend_mode,
+        set_blend_mode,
+        BlendMode = blend_mode_from_u32,
+        BlendMode
+    );
+}
+
+try_from_component!(Image, Image);
@@ -2,20 +2,73 @@ use core::{marker::PhantomData, ptr, slice, str};
 
 use crate::{
     ffi,
+    path::FillRule,
     raw_iter::{impl_iter, Raw},
+    renderer::{BlendMode, StrokeCap, StrokeJoin},
 };
 
+mod bone;
+mod event;
+mod image;
+mod nested_artboard;
+mod node;
+mod paint;
+mod shape;
+mod text;
 mod text_value_run;
 
-pub use text_value_run::TextValueRun;
+pub use self::{
+    bone::Bone,
+    event::Event,
+    image::Image,
+    nested_artboard::NestedArtboard,
+    node::Node,
+    paint::{Fill, SolidColor, Stroke},
+    shape::Shape,
+    text::Text,
+    text_value_run::TextValueRun,
+};
+pub(crate) use nested_artboard::nested_artboard_at_path;
+
+/// Type of a [`Component`]. Only the types that have a typed counterpart in this module are
+/// listed, all others are [`ComponentKind::Other`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ComponentKind {
+    Node,
+    Shape,
+    Fill,
+    Stroke,
+    SolidColor,
+    Bone,
+    Image,
+    Text,
+    TextValueRun,
+    Event,
+    NestedArtboard,
+    Other,
+}
 
 pub struct Component<'a> {
     raw_component: *mut ffi::Component,
     _phantom: PhantomData<&'a ()>,
 }
 
-impl Component<'_> {
-    pub fn name(&self) -> &str {
+impl<'a> Component<'a> {
+    /// Returns the type key of the component in the Rive format.
+    #[inline]
+    pub fn type_id(&self) -> u16 {
+        unsafe { ffi::rive_rs_component_type_id(self.raw_component) }
+    }
+
+    /// Returns the exact type of the component. Converting the component with [`TryFrom`] also
+    /// succeeds for sub-types, e.g. a [`Shape`] can be converted to a [`Node`].
+    #[inline]
+    pub fn kind(&self) -> ComponentKind {
+        unsafe { ffi::rive_rs_component_kind(self.raw_component) }
+    }
+
+    pub fn name(&self) -> &'a str {
         let mut data = ptr::null();
         let mut len = 0;
 
@@ -30,6 +83,11 @@ impl Component<'_> {
 
         str::from_utf8(bytes).expect("component name is invalid UTF-8")
     }
+
+    /// Hit tests the component at artboard space coordinates.
+    pub(crate) fn hit_test(&self, x: f32, y: f32) -> bool {
+        unsafe { ffi::rive_rs_component_hit_test(self.raw_component, x, y) }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -53,7 +111,15 @@ impl Raw for RawArtboard {
 impl_iter!(Components, Component, RawArtboard, 'a);
 
 macro_rules! try_from_component {
-    ( $component:ident, $raw_name:ident, $type_id:expr ) => {
+    ( $component:ident, $kind:ident ) => {
+        try_from_component!($component, raw_component, Component, $kind);
+    };
+
+    ( $component:ident, $raw_name:ident, $kind:ident ) => {
+        try_from_component!($component, $raw_name, $component, $kind);
+    };
+
+    ( $component:ident, $raw_name:ident, $raw_type:ident, $kind:ident ) => {
         impl<'a> TryFrom<crate::artboard::components::Component<'a>> for $component<'a> {
             type Error = ();
 
@@ -61,12 +127,15 @@ macro_rules! try_from_component {
                 value: crate::artboard::components::Component<'a>,
             ) -> Result<Self, Self::Error> {
                 unsafe {
-                    (crate::ffi::rive_rs_component_type_id(value.raw_component) == $type_id)
-                        .then(|| Self {
-                            $raw_name: value.raw_component as *mut crate::ffi::$component,
-                            _phantom: ::core::marker::PhantomData,
-                        })
-                        .ok_or(())
+                    crate::ffi::rive_rs_component_is(
+                        value.raw_component,
+                        crate::artboard::components::ComponentKind::$kind,
+                    )
+                    .then(|| Self {
+                        $raw_name: value.raw_component as *mut crate::ffi::$raw_type,
+                        _phantom: ::core::marker::PhantomData,
+                    })
+                    .ok_or(())
                 }
             }
         }
@@ -74,3 +143,121 @@ macro_rules! try_from_component {
 }
 
 pub(crate) use try_from_component;
+
+/// Implements a getter and a setter for a property of a component stored as `raw_component`.
+macro_rules! component_property {
+    ( $get:ident, $set:ident, f32, $property:ident ) => {
+        #[inline]
+        pub fn $get(&self) -> f32 {
+            unsafe {
+                crate::ffi::rive_rs_component_get_f32(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                )
+            }
+        }
+
+        #[inline]
+        pub fn $set(&mut self, val: f32) {
+            unsafe {
+                crate::ffi::rive_rs_component_set_f32(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                    val,
+                );
+            }
+        }
+    };
+
+    ( $get:ident, $set:ident, $type:ty = $from_u32:path, $property:ident ) => {
+        #[inline]
+        pub fn $get(&self) -> $type {
+            $from_u32(unsafe {
+                crate::ffi::rive_rs_component_get_u32(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                )
+            })
+        }
+
+        #[inline]
+        pub fn $set(&mut self, val: $type) {
+            unsafe {
+                crate::ffi::rive_rs_component_set_u32(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                    val as u32,
+                );
+            }
+        }
+    };
+
+    ( $get:ident, $set:ident, bool, $property:ident ) => {
+        #[inline]
+        pub fn $get(&self) -> bool {
+            unsafe {
+                crate::ffi::rive_rs_component_get_bool(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                )
+            }
+        }
+
+        #[inline]
+        pub fn $set(&mut self, val: bool) {
+            unsafe {
+                crate::ffi::rive_rs_component_set_bool(
+                    self.raw_component,
+                    crate::ffi::ComponentProperty::$property,
+                    val,
+                );
+            }
+        }
+    };
+}
+
+pub(crate) use component_property;
+
+fn blend_mode_from_u32(val: u32) -> BlendMode {
+    match val {
+        14 => BlendMode::Screen,
+        15 => BlendMode::Overlay,
+        16 => BlendMode::Darken,
+        17 => BlendMode::Lighten,
+        18 => BlendMode::ColorDodge,
+        19 => BlendMode::ColorBurn,
+        20 => BlendMode::HardLight,
+        21 => BlendMode::SoftLight,
+        22 => BlendMode::Difference,
+        23 => BlendMode::Exclusion,
+        24 => BlendMode::Multiply,
+        25 => BlendMode::Hue,
+        26 => BlendMode::Saturation,
+        27 => BlendMode::Color,
+        28 => BlendMode::Luminosity,
+        _ => BlendMode::SrcOver,
+    }
+}
+
+fn fill_rule_from_u32(val: u32) -> FillRule {
+    match val {
+        1 => FillRule::EvenOdd,
+        _ => FillRule::NonZero,
+    }
+}
+
+fn stroke_cap_from_u32(val: u32) -> StrokeCap {
+    match val {
+        1 => StrokeCap::Round,
+        2 => StrokeCap::Square,
+        _ => StrokeCap::Butt,
+    }
+}
+
+fn stroke_join_from_u32(val: u32) -> StrokeJoin {
+    match val {
+        1 => StrokeJoin::Round,
+        2 => StrokeJoin::Bevel,
+        _ => StrokeJoin::Miter,
+    }
+}
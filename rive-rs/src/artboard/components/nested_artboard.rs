@@ -0,0 +1,99 @@
+use core::{marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    ffi,
+    state_machine::{Bool, Number, Trigger},
+};
+
+use super::{try_from_component, Components, Node, RawArtboard};
+
+/// An artboard instanced inside of another artboard.
+///
+/// The inputs are those of the state machines playing inside of the nested artboard.
+pub struct NestedArtboard<'a> {
+    raw_nested_artboard: *mut ffi::NestedArtboard,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> NestedArtboard<'a> {
+    pub(crate) fn new(raw_nested_artboard: *mut ffi::NestedArtboard) -> Self {
+        Self {
+            raw_nested_artboard,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn raw_artboard(&self) -> Option<NonNull<ffi::Artboard>> {
+        NonNull::new(unsafe { ffi::rive_rs_nested_artboard_artboard(self.raw_nested_artboard) })
+    }
+
+    #[inline]
+    pub fn node(&mut self) -> Node {
+        Node::new(self.raw_nested_artboard as *mut ffi::Component)
+    }
+
+    /// Iterates over the components of the nested artboard. Returns `None` if the artboard it
+    /// references is missing from the file.
+    #[inline]
+    pub fn components(&mut self) -> Option<Components> {
+        self.raw_artboard()
+            .map(|raw_artboard| Components::new(RawArtboard(raw_artboard.as_ptr())))
+    }
+
+    /// Looks up an artboard nested inside of this one by its path of `/`-separated names,
+    /// relative to this artboard.
+    #[inline]
+    pub fn nested_artboard_at_path(&mut self, path: &str) -> Option<NestedArtboard> {
+        let raw_artboard = self.raw_artboard()?;
+
+        nested_artboard_at_path(raw_artboard.as_ptr(), path)
+    }
+
+    #[inline]
+    pub fn get_bool(&self, name: &str) -> Option<Bool<'a>> {
+        unsafe {
+            NonNull::new(ffi::rive_rs_nested_artboard_get_bool(
+                self.raw_nested_artboard,
+                name.as_ptr(),
+                name.len(),
+            ))
+            .map(|ptr| Bool::new(ptr.as_ptr()))
+        }
+    }
+
+    #[inline]
+    pub fn get_number(&self, name: &str) -> Option<Number<'a>> {
+        unsafe {
+            NonNull::new(ffi::rive_rs_nested_artboard_get_number(
+                self.raw_nested_artboard,
+                name.as_ptr(),
+                name.len(),
+            ))
+            .map(|ptr| Number::new(ptr.as_ptr()))
+        }
+    }
+
+    #[inline]
+    pub fn get_trigger(&self, name: &str) -> Option<Trigger<'a>> {
+        unsafe {
+            NonNull::new(ffi::rive_rs_nested_artboard_get_trigger(
+                self.raw_nested_artboard,
+                name.as_ptr(),
+                name.len(),
+            ))
+            .map(|ptr| Trigger::new(ptr.as_ptr()))
+        }
+    }
+}
+
+pub(crate) fn nested_artboard_at_path<'a>(
+    raw_artboard: *mut ffi::Artboard,
+    path: &str,
+) -> Option<NestedArtboard<'a>> {
+    NonNull::new(unsafe {
+        ffi::rive_rs_artboard_nested_artboard_at_path(raw_artboard, path.as_ptr(), path.len())
+    })
+    .map(|ptr| NestedArtboard::new(ptr.as_ptr()))
+}
+
+try_from_component!(NestedArtboard, raw_nested_artboard, NestedArtboard);
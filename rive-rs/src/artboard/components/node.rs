@@ -0,0 +1,31 @@
+use core::marker::PhantomData;
+
+use crate::ffi;
+
+use super::{component_property, try_from_component};
+
+/// A component with a transform relative to its parent. Shapes, images, texts and nested
+/// artboards are all nodes.
+pub struct Node<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Node<'_> {
+    pub(crate) fn new(raw_component: *mut ffi::Component) -> Self {
+        Self {
+            raw_component,
+            _phantom: PhantomData,
+        }
+    }
+
+    component_property!(x, set_x, f32, X);
+    component_property!(y, set_y, f32, Y);
+    // Rotation in radians.
+    component_property!(rotation, set_rotation, f32, Rotation);
+    component_property!(scale_x, set_scale_x, f32, ScaleX);
+    component_property!(scale_y, set_scale_y, f32, ScaleY);
+    component_property!(opacity, set_opacity, f32, Opacity);
+}
+
+try_from_component!(Node, Node);
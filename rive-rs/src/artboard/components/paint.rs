@@ -0,0 +1,73 @@
+use core::marker::PhantomData;
+
+use crate::{
+    ffi,
+    path::FillRule,
+    renderer::{Color, StrokeCap, StrokeJoin},
+};
+
+use super::{
+    component_property, fill_rule_from_u32, stroke_cap_from_u32, stroke_join_from_u32,
+    try_from_component,
+};
+
+/// Paints the inside of the paths of its [`Shape`](super::Shape).
+pub struct Fill<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Fill<'_> {
+    component_property!(is_visible, set_is_visible, bool, IsVisible);
+    component_property!(
+        fill_rule,
+        set_fill_rule,
+        FillRule = fill_rule_from_u32,
+        FillRule
+    );
+}
+
+try_from_component!(Fill, Fill);
+
+/// Paints the outline of the paths of its [`Shape`](super::Shape).
+pub struct Stroke<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Stroke<'_> {
+    component_property!(is_visible, set_is_visible, bool, IsVisible);
+    component_property!(thickness, set_thickness, f32, Thickness);
+    component_property!(cap, set_cap, StrokeCap = stroke_cap_from_u32, Cap);
+    component_property!(join, set_join, StrokeJoin = stroke_join_from_u32, Join);
+}
+
+try_from_component!(Stroke, Stroke);
+
+/// The color of a [`Fill`] or [`Stroke`].
+pub struct SolidColor<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl SolidColor<'_> {
+    #[inline]
+    pub fn color(&self) -> Color {
+        unsafe {
+            ffi::rive_rs_component_get_color(self.raw_component, ffi::ComponentProperty::Color)
+        }
+    }
+
+    #[inline]
+    pub fn set_color(&mut self, color: Color) {
+        unsafe {
+            ffi::rive_rs_component_set_color(
+                self.raw_component,
+                ffi::ComponentProperty::Color,
+                color,
+            );
+        }
+    }
+}
+
+try_from_component!(SolidColor, SolidColor);
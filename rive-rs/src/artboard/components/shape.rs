@@ -0,0 +1,28 @@
+use core::marker::PhantomData;
+
+use crate::{ffi, renderer::BlendMode};
+
+use super::{blend_mode_from_u32, component_property, try_from_component, Node};
+
+/// A drawable made out of paths and painted by its [`Fill`](super::Fill)s and
+/// [`Stroke`](super::Stroke)s.
+pub struct Shape<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Shape<'_> {
+    #[inline]
+    pub fn node(&mut self) -> Node {
+        Node::new(self.raw_component)
+    }
+
+    component_property!(
+        blend_mode,
+        set_blend_mode,
+        BlendMode = blend_mode_from_u32,
+        BlendMode
+    );
+}
+
+try_from_component!(Shape, Shape);
@@ -0,0 +1,30 @@
+use core::marker::PhantomData;
+
+use crate::{ffi, renderer::BlendMode};
+
+use super::{blend_mode_from_u32, component_property, try_from_component, Node};
+
+/// A text drawable. Its contents are stored in its [`TextValueRun`](super::TextValueRun)s.
+pub struct Text<'a> {
+    raw_component: *mut ffi::Component,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl Text<'_> {
+    #[inline]
+    pub fn node(&mut self) -> Node {
+        Node::new(self.raw_component)
+    }
+
+    component_property!(
+        blend_mode,
+        set_blend_mode,
+        BlendMode = blend_mode_from_u32,
+        BlendMode
+    );
+    // Size of the text box, used unless the text is sized automatically.
+    component_property!(width, set_width, f32, Width);
+    component_property!(height, set_height, f32, Height);
+}
+
+try_from_component!(Text, Text);
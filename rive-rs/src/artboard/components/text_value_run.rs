@@ -37,4 +37,4 @@ impl TextValueRun<'_> {
     }
 }
 
-try_from_component!(TextValueRun, raw_text_value_run, 135);
+try_from_component!(TextValueRun, raw_text_value_run, TextValueRun);
@@ -1,23 +1,52 @@
 use alloc::sync::Arc;
-use core::{fmt, marker::PhantomData, ptr::NonNull};
+use core::{fmt, marker::PhantomData, ptr::NonNull, time::Duration};
 
 use crate::{
     ffi,
-    file::{File, FileInner},
+    file::{AssetLock, File, FileInner},
     instantiate::{Handle, Instantiate},
+    path::Point,
     renderer::Renderer,
+    scene::{self, Viewport},
 };
 
-use self::components::Components;
+use self::components::{Components, NestedArtboard};
 
 pub mod components;
 
+/// Axis-aligned bounding box in artboard space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ArtboardInner {
-    _file: Arc<FileInner>,
+    file: Arc<FileInner>,
     pub(crate) raw_artboard: *mut ffi::Artboard,
 }
 
+impl ArtboardInner {
+    /// Lock that draws and advances of the artboard take, so that the assets of its file are not
+    /// swapped under them.
+    pub(crate) fn assets_lock(&self) -> &AssetLock {
+        &self.file.assets_lock
+    }
+}
+
 impl Drop for ArtboardInner {
     fn drop(&mut self) {
         unsafe {
@@ -50,6 +79,110 @@ impl<R: Renderer> Artboard<R> {
     pub fn components(&mut self) -> Components {
         Components::new(components::RawArtboard(self.inner.raw_artboard))
     }
+
+    /// Looks up a nested artboard by its path of `/`-separated names, e.g. `"button/icon"`
+    /// for the artboard `icon` nested inside of the artboard `button`.
+    #[inline]
+    pub fn nested_artboard_at_path(&mut self, path: &str) -> Option<NestedArtboard> {
+        components::nested_artboard_at_path(self.inner.raw_artboard, path)
+    }
+
+    /// Iterates over the names of the shapes under viewport coordinates `x`, `y`, using the same
+    /// transform as [`Scene`](crate::scene::Scene) pointer events. Shapes are yielded in the
+    /// order of [`Artboard::components`], which doesn't necessarily match their draw order.
+    pub fn components_at(&self, x: f32, y: f32, viewport: &Viewport) -> impl Iterator<Item = &str> {
+        let (_, inverse_view_transform) = scene::view_transforms(self.inner.raw_artboard, viewport);
+        let [x, y] = scene::transform(x, y, &inverse_view_transform);
+
+        Components::new(components::RawArtboard(self.inner.raw_artboard))
+            .filter(move |component| component.hit_test(x, y))
+            .map(|component| component.name())
+    }
+
+    /// Advances the artboard's components, e.g. after animations have been applied to it
+    /// manually, without going through a [`Scene`](crate::scene::Scene). Returns `true` if
+    /// anything changed.
+    #[inline]
+    pub fn advance(&mut self, elapsed: Duration) -> bool {
+        self.inner.assets_lock().read(|| unsafe {
+            ffi::rive_rs_artboard_advance(self.inner.raw_artboard, elapsed.as_secs_f32())
+        })
+    }
+
+    /// Draws the artboard in artboard space with `renderer`'s current transform.
+    #[inline]
+    pub fn draw(&self, renderer: &mut R) {
+        self.inner.assets_lock().read(|| unsafe {
+            ffi::rive_rs_artboard_draw(
+                self.inner.raw_artboard,
+                renderer as *mut R as *mut (),
+                ffi::RendererEntries::<R>::ENTRIES as *const ffi::RendererEntries<R> as *const (),
+            );
+        });
+    }
+
+    /// Bounds of the artboard, offset by its [`origin`](Self::origin).
+    pub fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds {
+            min: Point { x: 0.0, y: 0.0 },
+            max: Point { x: 0.0, y: 0.0 },
+        };
+
+        unsafe {
+            ffi::rive_rs_artboard_bounds(
+                self.inner.raw_artboard,
+                &mut bounds.min.x,
+                &mut bounds.min.y,
+                &mut bounds.max.x,
+                &mut bounds.max.y,
+            );
+        }
+
+        bounds
+    }
+
+    #[inline]
+    pub fn width(&self) -> f32 {
+        unsafe { ffi::rive_rs_artboard_width(self.inner.raw_artboard) }
+    }
+
+    #[inline]
+    pub fn set_width(&mut self, width: f32) {
+        unsafe {
+            ffi::rive_rs_artboard_set_width(self.inner.raw_artboard, width);
+        }
+    }
+
+    #[inline]
+    pub fn height(&self) -> f32 {
+        unsafe { ffi::rive_rs_artboard_height(self.inner.raw_artboard) }
+    }
+
+    #[inline]
+    pub fn set_height(&mut self, height: f32) {
+        unsafe {
+            ffi::rive_rs_artboard_set_height(self.inner.raw_artboard, height);
+        }
+    }
+
+    /// Origin of the artboard, relative to its size.
+    #[inline]
+    pub fn origin(&self) -> Point {
+        let mut origin = Point { x: 0.0, y: 0.0 };
+
+        unsafe {
+            ffi::rive_rs_artboard_origin(self.inner.raw_artboard, &mut origin.x, &mut origin.y);
+        }
+
+        origin
+    }
+
+    #[inline]
+    pub fn set_origin(&mut self, origin: Point) {
+        unsafe {
+            ffi::rive_rs_artboard_set_origin(self.inner.raw_artboard, origin.x, origin.y);
+        }
+    }
 }
 
 impl<R: Renderer> Instantiate for Artboard<R> {
@@ -82,7 +215,7 @@ impl<R: Renderer> Instantiate for Artboard<R> {
 
         raw_artboard.map(|raw_artboard| Artboard {
             inner: Arc::new(ArtboardInner {
-                _file: file.as_inner().clone(),
+                file: file.as_inner().clone(),
                 raw_artboard: raw_artboard.as_ptr(),
             }),
             _phantom: PhantomData,
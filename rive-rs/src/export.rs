@@ -0,0 +1,309 @@
+//! Renders the frames of a [linear animation](crate::LinearAnimation) with the
+//! [`software`](crate::software) back-end and encodes them as PNG sequences, animated PNGs or
+//! GIFs, e.g. to generate previews.
+
+use std::{error, fmt, io, time::Duration};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageError, RgbaImage,
+};
+
+use crate::{
+    artboard::Artboard,
+    file,
+    instantiate::{Handle, Instantiate},
+    linear_animation::{LinearAnimation, Loop},
+    renderer::{Color, Renderer as _},
+    scene::{self, Alignment, Fit, Scene as _, Viewport},
+    software::Renderer,
+};
+
+/// A [`File`](crate::File) whose artboards are drawn with the [`software`](crate::software)
+/// back-end, as needed by [`Exporter::new`]. Unlike [`crate::File`], it is available even when the
+/// `vello` feature is enabled.
+pub type File = file::File<Renderer>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The artboard could not be found in the file.
+    Artboard,
+    /// The animation could not be found in the artboard.
+    Animation,
+    /// The frame size or frame rate is zero.
+    InvalidOptions,
+    Io(io::Error),
+    Png(png::EncodingError),
+    Gif(ImageError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Artboard => write!(f, "artboard not found"),
+            Self::Animation => write!(f, "animation not found"),
+            Self::InvalidOptions => write!(f, "frame size and frame rate must be non-zero"),
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Png(error) => write!(f, "{error}"),
+            Self::Gif(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Png(error) => Some(error),
+            Self::Gif(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(error: png::EncodingError) -> Self {
+        Self::Png(error)
+    }
+}
+
+impl From<ImageError> for Error {
+    fn from(error: ImageError) -> Self {
+        Self::Gif(error)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    /// Color every frame is cleared with before the animation is drawn on top of it.
+    pub background: Color,
+    pub fit: Fit,
+    pub alignment: Alignment,
+}
+
+impl Default for Options {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            width: 512,
+            height: 512,
+            fps: 30,
+            background: Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+            fit: Fit::default(),
+            alignment: Alignment::default(),
+        }
+    }
+}
+
+/// Renders the frames of a [linear animation](crate::LinearAnimation) deterministically, i.e.
+/// frame `i` always shows the animation at `i / fps` seconds, independently of the frames
+/// rendered before it.
+///
+/// The number of frames depends on the [`Loop`] of the animation and on its duration, limited to
+/// its work area if it has one enabled:
+///
+/// * [`Loop::OneShot`] animations are rendered up to and including their last frame.
+/// * [`Loop::Loop`] animations stop one frame before their end, which is the same as their start.
+/// * [`Loop::PingPong`] animations are rendered forwards and then backwards.
+pub struct Exporter {
+    animation: LinearAnimation<Renderer>,
+    renderer: Renderer,
+    viewport: Viewport,
+    background: Color,
+    fps: u32,
+    start: Duration,
+    duration: Duration,
+    r#loop: Loop,
+}
+
+impl Exporter {
+    pub fn new(
+        file: &File,
+        artboard: Handle,
+        animation: Handle,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        if options.fps == 0 {
+            return Err(Error::InvalidOptions);
+        }
+
+        let renderer = Renderer::new(options.width, options.height).ok_or(Error::InvalidOptions)?;
+        let artboard = Artboard::instantiate(file, artboard).ok_or(Error::Artboard)?;
+        let animation =
+            LinearAnimation::instantiate(&artboard, animation).ok_or(Error::Animation)?;
+
+        let mut viewport = Viewport::default();
+        viewport.resize(options.width, options.height);
+        viewport.set_fit(options.fit);
+        viewport.set_alignment(options.alignment);
+
+        Ok(Self {
+            // A new instance starts at the beginning of its work area.
+            start: animation.time(),
+            duration: animation.duration().unwrap_or_default(),
+            r#loop: animation.r#loop(),
+            animation,
+            renderer,
+            viewport,
+            background: options.background,
+            fps: options.fps,
+        })
+    }
+
+    #[inline]
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    pub fn frame_count(&self) -> usize {
+        let frames = self.duration.as_secs_f64() * self.fps as f64;
+
+        match self.r#loop {
+            Loop::OneShot => frames.round() as usize + 1,
+            Loop::Loop => (frames.ceil() as usize).max(1),
+            Loop::PingPong => ((frames * 2.0).ceil() as usize).max(1),
+        }
+    }
+
+    fn frame_time(&self, index: usize) -> Duration {
+        let time = Duration::from_secs_f64(index as f64 / self.fps as f64);
+
+        let time = match self.r#loop {
+            Loop::OneShot => time.min(self.duration),
+            Loop::Loop => time,
+            Loop::PingPong if time > self.duration => (self.duration * 2).saturating_sub(time),
+            Loop::PingPong => time,
+        };
+
+        self.start + time
+    }
+
+    /// Renders frame `index` and returns the renderer containing it.
+    pub fn render_frame(&mut self, index: usize) -> &Renderer {
+        let time = self.frame_time(index);
+
+        scene::resize_to_viewport(self.animation.raw_artboard(), &self.viewport);
+        self.animation.set_time(time);
+        // Advancing by zero applies the animation at its current time and updates the artboard.
+        self.animation.advance_and_apply(Duration::ZERO);
+
+        let (view_transform, _) =
+            scene::view_transforms(self.animation.raw_artboard(), &self.viewport);
+
+        self.renderer.clear(self.background);
+
+        self.renderer.state_push();
+        self.renderer.transform(&view_transform);
+        self.animation.draw(&mut self.renderer);
+        self.renderer.state_pop();
+
+        &self.renderer
+    }
+
+    fn render_rgba8(&mut self, index: usize) -> Vec<u8> {
+        self.render_frame(index).to_rgba8()
+    }
+
+    fn png_encoder<W: io::Write>(&self, writer: W) -> png::Encoder<'static, W> {
+        let mut encoder = png::Encoder::new(writer, self.viewport.width(), self.viewport.height());
+
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+    }
+
+    /// Writes frame `index` as a PNG image.
+    pub fn write_png<W: io::Write>(&mut self, index: usize, writer: W) -> Result<(), Error> {
+        let data = self.render_rgba8(index);
+
+        let mut writer = self.png_encoder(writer).write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes every frame as a separate PNG image into the writer that `writer_for_frame`
+    /// returns for its index.
+    pub fn write_png_sequence<W: io::Write>(
+        &mut self,
+        mut writer_for_frame: impl FnMut(usize) -> io::Result<W>,
+    ) -> Result<(), Error> {
+        for index in 0..self.frame_count() {
+            self.write_png(index, writer_for_frame(index)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes all frames as an animated PNG. Animations that don't loop are only played once.
+    pub fn write_apng<W: io::Write>(&mut self, writer: W) -> Result<(), Error> {
+        let frame_count = self.frame_count();
+        let plays = match self.r#loop {
+            Loop::OneShot => 1,
+            Loop::Loop | Loop::PingPong => 0,
+        };
+
+        let mut encoder = self.png_encoder(writer);
+        encoder.set_animated(frame_count as u32, plays)?;
+        encoder.set_frame_delay(1, self.fps.min(u16::MAX as u32) as u16)?;
+
+        let mut writer = encoder.write_header()?;
+        for index in 0..frame_count {
+            let data = self.render_rgba8(index);
+            writer.write_image_data(&data)?;
+        }
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes all frames as an animated GIF. Animations that don't loop are only played once.
+    ///
+    /// GIFs don't support partial transparency, so a fully opaque `background` is recommended.
+    pub fn write_gif<W: io::Write>(&mut self, writer: W) -> Result<(), Error> {
+        let (width, height) = (self.viewport.width(), self.viewport.height());
+        let delay = Delay::from_numer_denom_ms(1000, self.fps);
+
+        let mut encoder = GifEncoder::new(writer);
+        // GIFs without a repeat count are played once.
+        if self.r#loop != Loop::OneShot {
+            encoder.set_repeat(Repeat::Infinite)?;
+        }
+
+        for index in 0..self.frame_count() {
+            let buffer = RgbaImage::from_raw(width, height, self.render_rgba8(index))
+                .expect("frame has the size of the viewport");
+            encoder.encode_frame(Frame::from_parts(buffer, 0, 0, delay))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Exporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exporter")
+            .field("viewport", &self.viewport)
+            .field("fps", &self.fps)
+            .field("start", &self.start)
+            .field("duration", &self.duration)
+            .field("loop", &self.r#loop)
+            .finish()
+    }
+}
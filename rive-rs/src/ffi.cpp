@@ -1,21 +1,59 @@
+#include "rive/animation/animation_state.hpp"
+#include "rive/animation/any_state.hpp"
+#include "rive/animation/blend_animation.hpp"
+#include "rive/animation/blend_state.hpp"
+#include "rive/animation/entry_state.hpp"
+#include "rive/animation/exit_state.hpp"
+#include "rive/animation/layer_state.hpp"
+#include "rive/animation/linear_animation.hpp"
 #include "rive/animation/linear_animation_instance.hpp"
+#include "rive/animation/state_machine.hpp"
 #include "rive/animation/state_machine_bool.hpp"
 #include "rive/animation/state_machine_input.hpp"
 #include "rive/animation/state_machine_input_instance.hpp"
 #include "rive/animation/state_machine_instance.hpp"
+#include "rive/animation/state_machine_layer.hpp"
 #include "rive/animation/state_machine_number.hpp"
 #include "rive/animation/state_machine_trigger.hpp"
+#include "rive/animation/state_transition.hpp"
+#include "rive/assets/audio_asset.hpp"
+#include "rive/assets/file_asset.hpp"
+#include "rive/assets/font_asset.hpp"
+#include "rive/assets/image_asset.hpp"
+#include "rive/bones/bone.hpp"
+#include "rive/bones/root_bone.hpp"
+#include "rive/core/binary_reader.hpp"
+#include "rive/core/field_types/core_color_type.hpp"
+#include "rive/core/field_types/core_double_type.hpp"
+#include "rive/core/field_types/core_string_type.hpp"
+#include "rive/core/field_types/core_uint_type.hpp"
 #include "rive/custom_property_boolean.hpp"
 #include "rive/custom_property_number.hpp"
 #include "rive/custom_property_string.hpp"
+#include "rive/drawable.hpp"
 #include "rive/event.hpp"
 #include "rive/factory.hpp"
 #include "rive/file.hpp"
+#include "rive/file_asset_loader.hpp"
+#include "rive/generated/core_registry.hpp"
+#include "rive/layout.hpp"
+#include "rive/math/aabb.hpp"
 #include "rive/math/path_types.hpp"
 #include "rive/math/raw_path.hpp"
 #include "rive/math/vec2d.hpp"
+#include "rive/nested_artboard.hpp"
+#include "rive/node.hpp"
 #include "rive/renderer.hpp"
+#include "rive/runtime_header.hpp"
+#include "rive/shapes/image.hpp"
+#include "rive/shapes/paint/fill.hpp"
+#include "rive/shapes/paint/solid_color.hpp"
+#include "rive/shapes/paint/stroke.hpp"
+#include "rive/shapes/shape.hpp"
+#include "rive/text/text.hpp"
 #include "rive/text/text_value_run.hpp"
+#include "rive/transform_component.hpp"
+#include "rive/world_transform_component.hpp"
 
 extern "C"
 {
@@ -30,6 +68,7 @@ extern "C"
     typedef struct RawRustRenderer RawRustRenderer;
     typedef struct RawRustString RawRustString;
     typedef struct RawRustBTreeMap RawRustBTreeMap;
+    typedef struct RawRustAssetLoader RawRustAssetLoader;
 
     void rive_rs_allocate_string(const RawRustString* string,
                                  const char* data,
@@ -59,6 +98,56 @@ extern "C"
                                  PropertyTag value_tag,
                                  Property value_payload);
 
+    bool rive_rs_asset_loader_load(const RawRustAssetLoader* asset_loader,
+                                   FileAsset* asset,
+                                   const uint8_t* in_band_data,
+                                   size_t in_band_len,
+                                   Factory* factory);
+
+    enum class RustImageWrap : uint8_t
+    {
+        Clamp,
+        Repeat,
+        Mirror,
+    };
+
+    enum class RustImageFilter : uint8_t
+    {
+        Bilinear,
+        Nearest,
+        Mipmap,
+    };
+
+    typedef struct RustImageSampler
+    {
+        RustImageWrap wrap_x;
+        RustImageWrap wrap_y;
+        RustImageFilter filter;
+    } RustImageSampler;
+
+    static RustImageWrap rive_rs_image_wrap(ImageWrap wrap)
+    {
+        switch (wrap)
+        {
+            case ImageWrap::repeat:
+                return RustImageWrap::Repeat;
+            case ImageWrap::mirror:
+                return RustImageWrap::Mirror;
+            default:
+                return RustImageWrap::Clamp;
+        }
+    }
+
+    static RustImageSampler rive_rs_image_sampler(ImageSampler sampler)
+    {
+        return {
+            rive_rs_image_wrap(sampler.wrapX),
+            rive_rs_image_wrap(sampler.wrapY),
+            sampler.filter == ImageFilter::nearest ? RustImageFilter::Nearest
+                                                   : RustImageFilter::Bilinear,
+        };
+    }
+
     typedef struct RendererEntries
     {
         const RawRustBuffer* (*buffer_new)(RenderBufferType,
@@ -120,10 +209,12 @@ extern "C"
                                    const RawRustPaint*);
         void (*renderer_draw_image)(const RawRustRenderer*,
                                     const RawRustImage*,
+                                    RustImageSampler,
                                     BlendMode,
                                     float);
         void (*renderer_draw_image_mesh)(const RawRustRenderer*,
                                          const RawRustImage*,
+                                         RustImageSampler,
                                          const RawRustBuffer*,
                                          const RawRustBuffer*,
                                          const RawRustBuffer*,
@@ -368,6 +459,28 @@ extern "C"
         }
     };
 
+    class RustFileAssetLoader : public FileAssetLoader
+    {
+    private:
+        const RawRustAssetLoader* m_asset_loader;
+
+    public:
+        RustFileAssetLoader(const RawRustAssetLoader* asset_loader) :
+            m_asset_loader(asset_loader)
+        {}
+
+        bool loadContents(FileAsset& asset,
+                          Span<const uint8_t> inBandBytes,
+                          Factory* factory) override
+        {
+            return rive_rs_asset_loader_load(m_asset_loader,
+                                             &asset,
+                                             inBandBytes.data(),
+                                             inBandBytes.size(),
+                                             factory);
+        }
+    };
+
     class RustRenderer : public Renderer
     {
     private:
@@ -408,6 +521,7 @@ extern "C"
             LITE_RTTI_CAST_OR_RETURN(rustImage, const RustImage*, image);
             m_entries->renderer_draw_image(m_renderer,
                                            rustImage->image(),
+                                           rive_rs_image_sampler(options),
                                            blend_mode,
                                            opacity);
         }
@@ -433,6 +547,7 @@ extern "C"
                                      indices_u16.get());
             m_entries->renderer_draw_image_mesh(m_renderer,
                                                 rustImage->image(),
+                                                rive_rs_image_sampler(options),
                                                 rustVertices->buffer(),
                                                 rustUVCoords->buffer(),
                                                 rustIndices->buffer(),
@@ -447,6 +562,27 @@ extern "C"
         const Vec2D* points;
     } Command;
 
+    typedef struct FileResult
+    {
+        ImportResult kind;
+        uint32_t major_version;
+        uint32_t minor_version;
+        uint32_t supported_major_version;
+        uint32_t supported_minor_version;
+        size_t offset;
+        bool has_offset;
+        uint16_t type_key;
+        bool has_type_key;
+    } FileResult;
+
+    enum class AssetType : uint8_t
+    {
+        Image,
+        Font,
+        Audio,
+        Unknown,
+    };
+
     enum class InputTag : uint8_t
     {
         Bool,
@@ -454,14 +590,168 @@ extern "C"
         Trigger,
     };
 
+    enum class ComponentKind : uint8_t
+    {
+        Node,
+        Shape,
+        Fill,
+        Stroke,
+        SolidColor,
+        Bone,
+        Image,
+        Text,
+        TextValueRun,
+        Event,
+        NestedArtboard,
+        Other,
+    };
+
+    enum class ComponentProperty : uint8_t
+    {
+        X,
+        Y,
+        Rotation,
+        ScaleX,
+        ScaleY,
+        Opacity,
+        BlendMode,
+        Color,
+        FillRule,
+        IsVisible,
+        Thickness,
+        Cap,
+        Join,
+        Length,
+        Width,
+        Height,
+    };
+
+    enum class StateKind : uint8_t
+    {
+        Entry,
+        Exit,
+        Any,
+        Animation,
+        Blend,
+        Unknown,
+    };
+
+    // Walks the objects of a file that failed to import the same way
+    // `File::import` does in order to find where and why reading failed.
+    void rive_rs_file_diagnose(Span<const uint8_t> bytes, FileResult* result)
+    {
+        result->supported_major_version = File::majorVersion;
+        result->supported_minor_version = File::minorVersion;
+
+        BinaryReader reader(bytes);
+        RuntimeHeader header;
+
+        if (!RuntimeHeader::read(reader, header))
+        {
+            result->offset = 0;
+            result->has_offset = true;
+            return;
+        }
+
+        result->major_version = header.majorVersion();
+        result->minor_version = header.minorVersion();
+
+        if (result->kind != ImportResult::malformed)
+        {
+            return;
+        }
+
+        while (!reader.reachedEnd())
+        {
+            size_t offset = reader.position() - bytes.data();
+            auto type_key = reader.readVarUintAs<uint16_t>();
+
+            if (reader.hasError())
+            {
+                result->offset = offset;
+                result->has_offset = true;
+                return;
+            }
+
+            while (true)
+            {
+                auto property_key = reader.readVarUintAs<uint16_t>();
+
+                if (reader.hasError())
+                {
+                    break;
+                }
+
+                if (property_key == 0)
+                {
+                    break;
+                }
+
+                int id = CoreRegistry::propertyFieldId(property_key);
+                if (id == -1)
+                {
+                    id = header.propertyFieldId(property_key);
+                }
+
+                switch (id)
+                {
+                    case CoreUintType::id:
+                        CoreUintType::deserialize(reader);
+                        break;
+                    case CoreStringType::id:
+                        CoreStringType::deserialize(reader);
+                        break;
+                    case CoreDoubleType::id:
+                        CoreDoubleType::deserialize(reader);
+                        break;
+                    case CoreColorType::id:
+                        CoreColorType::deserialize(reader);
+                        break;
+                    default:
+                        // Unknown property that is not in the ToC either.
+                        result->offset = offset;
+                        result->has_offset = true;
+                        result->type_key = type_key;
+                        result->has_type_key = true;
+                        return;
+                }
+            }
+
+            if (reader.hasError())
+            {
+                result->offset = offset;
+                result->has_offset = true;
+                result->type_key = type_key;
+                result->has_type_key = true;
+                return;
+            }
+        }
+    }
+
     const File* rive_rs_file_new(const uint8_t* data,
                                  size_t len,
                                  const RendererEntries* entries,
-                                 ImportResult* result,
+                                 const RawRustAssetLoader* asset_loader,
+                                 FileResult* result,
                                  RustFactory** factory)
     {
         RustFactory* rust_factory = new RustFactory(entries);
-        auto file = rive::File::import({data, len}, rust_factory, result);
+        rcp<FileAssetLoader> rust_asset_loader;
+
+        if (asset_loader)
+        {
+            rust_asset_loader = make_rcp<RustFileAssetLoader>(asset_loader);
+        }
+
+        auto file = rive::File::import({data, len},
+                                       rust_factory,
+                                       &result->kind,
+                                       rust_asset_loader);
+
+        if (result->kind != ImportResult::success)
+        {
+            rive_rs_file_diagnose({data, len}, result);
+        }
 
         *factory = rust_factory;
 
@@ -474,6 +764,227 @@ extern "C"
         delete factory;
     }
 
+    bool rive_rs_file_asset_decode(FileAsset* asset,
+                                   const uint8_t* data,
+                                   size_t len,
+                                   Factory* factory)
+    {
+        SimpleArray<uint8_t> bytes(data, len);
+        return asset->decode(bytes, factory);
+    }
+
+    bool rive_rs_file_load_asset(const File* file,
+                                 uint32_t id,
+                                 const uint8_t* data,
+                                 size_t len,
+                                 Factory* factory)
+    {
+        for (auto asset : file->assets())
+        {
+            if (asset->assetId() == id)
+            {
+                return rive_rs_file_asset_decode(&*asset, data, len, factory);
+            }
+        }
+
+        return false;
+    }
+
+    size_t rive_rs_file_asset_count(const File* file)
+    {
+        return file->assets().size();
+    }
+
+    FileAsset* rive_rs_file_get_asset(const File* file, size_t index)
+    {
+        return &*file->assets()[index];
+    }
+
+    uint32_t rive_rs_file_asset_id(const FileAsset* asset)
+    {
+        return asset->assetId();
+    }
+
+    void rive_rs_file_asset_name(const FileAsset* asset,
+                                 const char** data,
+                                 size_t* len)
+    {
+        *data = asset->name().data();
+        *len = asset->name().size();
+    }
+
+    void rive_rs_file_asset_unique_filename(const FileAsset* asset,
+                                            const RawRustString* string)
+    {
+        auto unique_filename = asset->uniqueFilename();
+        rive_rs_allocate_string(string,
+                                unique_filename.data(),
+                                unique_filename.size());
+    }
+
+    void rive_rs_file_asset_cdn_uuid(const FileAsset* asset,
+                                     const RawRustString* string)
+    {
+        auto cdn_uuid = asset->cdnUuidStr();
+        rive_rs_allocate_string(string, cdn_uuid.data(), cdn_uuid.size());
+    }
+
+    AssetType rive_rs_file_asset_type(const FileAsset* asset)
+    {
+        if (asset->is<ImageAsset>())
+        {
+            return AssetType::Image;
+        }
+
+        if (asset->is<FontAsset>())
+        {
+            return AssetType::Font;
+        }
+
+        if (asset->is<AudioAsset>())
+        {
+            return AssetType::Audio;
+        }
+
+        return AssetType::Unknown;
+    }
+
+    size_t rive_rs_file_artboard_count(const File* file)
+    {
+        return file->artboardCount();
+    }
+
+    const Artboard* rive_rs_file_artboard_at(const File* file, size_t index)
+    {
+        return file->artboard(index);
+    }
+
+    float rive_rs_artboard_descriptor_width(const Artboard* artboard)
+    {
+        return artboard->width();
+    }
+
+    float rive_rs_artboard_descriptor_height(const Artboard* artboard)
+    {
+        return artboard->height();
+    }
+
+    void rive_rs_artboard_descriptor_origin(const Artboard* artboard,
+                                            float* x,
+                                            float* y)
+    {
+        *x = artboard->originX();
+        *y = artboard->originY();
+    }
+
+    size_t rive_rs_artboard_descriptor_animation_count(const Artboard* artboard)
+    {
+        return artboard->animationCount();
+    }
+
+    const LinearAnimation* rive_rs_artboard_descriptor_animation_at(
+        const Artboard* artboard,
+        size_t index)
+    {
+        return artboard->animation(index);
+    }
+
+    size_t rive_rs_artboard_descriptor_state_machine_count(
+        const Artboard* artboard)
+    {
+        return artboard->stateMachineCount();
+    }
+
+    const StateMachine* rive_rs_artboard_descriptor_state_machine_at(
+        const Artboard* artboard,
+        size_t index)
+    {
+        return artboard->stateMachine(index);
+    }
+
+    void rive_rs_linear_animation_descriptor_name(
+        const LinearAnimation* linear_animation,
+        const char** data,
+        size_t* len)
+    {
+        *data = linear_animation->name().data();
+        *len = linear_animation->name().size();
+    }
+
+    float rive_rs_linear_animation_descriptor_duration(
+        const LinearAnimation* linear_animation)
+    {
+        return linear_animation->durationSeconds();
+    }
+
+    uint32_t rive_rs_linear_animation_descriptor_fps(
+        const LinearAnimation* linear_animation)
+    {
+        return linear_animation->fps();
+    }
+
+    Loop rive_rs_linear_animation_descriptor_loop(
+        const LinearAnimation* linear_animation)
+    {
+        return static_cast<Loop>(linear_animation->loopValue());
+    }
+
+    void rive_rs_state_machine_descriptor_name(
+        const StateMachine* state_machine,
+        const char** data,
+        size_t* len)
+    {
+        *data = state_machine->name().data();
+        *len = state_machine->name().size();
+    }
+
+    size_t rive_rs_state_machine_descriptor_input_count(
+        const StateMachine* state_machine)
+    {
+        return state_machine->inputCount();
+    }
+
+    const StateMachineInput* rive_rs_state_machine_descriptor_input_at(
+        const StateMachine* state_machine,
+        size_t index)
+    {
+        return state_machine->input(index);
+    }
+
+    void rive_rs_input_descriptor_name(const StateMachineInput* input,
+                                       const char** data,
+                                       size_t* len)
+    {
+        *data = input->name().data();
+        *len = input->name().size();
+    }
+
+    InputTag rive_rs_input_descriptor_tag(const StateMachineInput* input)
+    {
+        if (input->is<StateMachineBool>())
+        {
+            return InputTag::Bool;
+        }
+
+        if (input->is<StateMachineNumber>())
+        {
+            return InputTag::Number;
+        }
+
+        return InputTag::Trigger;
+    }
+
+    bool rive_rs_input_descriptor_bool_default(const StateMachineInput* input)
+    {
+        return input->as<StateMachineBool>()->value();
+    }
+
+    float rive_rs_input_descriptor_number_default(
+        const StateMachineInput* input)
+    {
+        return input->as<StateMachineNumber>()->value();
+    }
+
     void rive_rs_instantiate_artboard(const File* file,
                                       const size_t* index,
                                       ArtboardInstance** artboard_instance)
@@ -534,11 +1045,271 @@ extern "C"
         return artboard_instance->objects()[index];
     }
 
+    // Hit tests `component` at the artboard space position `x`, `y`. Only
+    // shapes can be hit.
+    bool rive_rs_component_hit_test(const Core* component, float x, float y)
+    {
+        if (!component->is<Shape>())
+        {
+            return false;
+        }
+
+        // Same hit area that state machine listeners use.
+        IAABB area = {static_cast<int32_t>(x - 1.0f),
+                      static_cast<int32_t>(y - 1.0f),
+                      static_cast<int32_t>(x + 1.0f),
+                      static_cast<int32_t>(y + 1.0f)};
+
+        return component->as<Shape>()->hitTest(area);
+    }
+
+    bool rive_rs_artboard_advance(ArtboardInstance* artboard_instance,
+                                  float elapsed)
+    {
+        return artboard_instance->advance(elapsed);
+    }
+
+    void rive_rs_artboard_draw(ArtboardInstance* artboard_instance,
+                               const RawRustRenderer* renderer,
+                               const RendererEntries* entries)
+    {
+        RustRenderer rust_renderer(renderer, entries);
+        artboard_instance->draw(&rust_renderer);
+    }
+
+    void rive_rs_artboard_bounds(const ArtboardInstance* artboard_instance,
+                                 float* min_x,
+                                 float* min_y,
+                                 float* max_x,
+                                 float* max_y)
+    {
+        auto bounds = artboard_instance->bounds();
+
+        *min_x = bounds.minX;
+        *min_y = bounds.minY;
+        *max_x = bounds.maxX;
+        *max_y = bounds.maxY;
+    }
+
+    float rive_rs_artboard_width(const ArtboardInstance* artboard_instance)
+    {
+        return artboard_instance->width();
+    }
+
+    void rive_rs_artboard_set_width(ArtboardInstance* artboard_instance,
+                                    float width)
+    {
+        artboard_instance->width(width);
+    }
+
+    float rive_rs_artboard_height(const ArtboardInstance* artboard_instance)
+    {
+        return artboard_instance->height();
+    }
+
+    void rive_rs_artboard_set_height(ArtboardInstance* artboard_instance,
+                                     float height)
+    {
+        artboard_instance->height(height);
+    }
+
+    void rive_rs_artboard_origin(const ArtboardInstance* artboard_instance,
+                                 float* x,
+                                 float* y)
+    {
+        *x = artboard_instance->originX();
+        *y = artboard_instance->originY();
+    }
+
+    void rive_rs_artboard_set_origin(ArtboardInstance* artboard_instance,
+                                     float x,
+                                     float y)
+    {
+        artboard_instance->originX(x);
+        artboard_instance->originY(y);
+    }
+
     uint16_t rive_rs_component_type_id(const Core* component)
     {
         return component->coreType();
     }
 
+    static uint16_t rive_rs_component_kind_type_key(ComponentKind kind)
+    {
+        switch (kind)
+        {
+            case ComponentKind::Node:
+                return NodeBase::typeKey;
+            case ComponentKind::Shape:
+                return ShapeBase::typeKey;
+            case ComponentKind::Fill:
+                return FillBase::typeKey;
+            case ComponentKind::Stroke:
+                return StrokeBase::typeKey;
+            case ComponentKind::SolidColor:
+                return SolidColorBase::typeKey;
+            case ComponentKind::Bone:
+                return BoneBase::typeKey;
+            case ComponentKind::Image:
+                return ImageBase::typeKey;
+            case ComponentKind::Text:
+                return TextBase::typeKey;
+            case ComponentKind::TextValueRun:
+                return TextValueRunBase::typeKey;
+            case ComponentKind::Event:
+                return EventBase::typeKey;
+            case ComponentKind::NestedArtboard:
+                return NestedArtboardBase::typeKey;
+            case ComponentKind::Other:
+                break;
+        }
+
+        return 0;
+    }
+
+    static uint16_t rive_rs_component_property_key(ComponentProperty property)
+    {
+        switch (property)
+        {
+            case ComponentProperty::X:
+                return NodeBase::xPropertyKey;
+            case ComponentProperty::Y:
+                return NodeBase::yPropertyKey;
+            case ComponentProperty::Rotation:
+                return TransformComponentBase::rotationPropertyKey;
+            case ComponentProperty::ScaleX:
+                return TransformComponentBase::scaleXPropertyKey;
+            case ComponentProperty::ScaleY:
+                return TransformComponentBase::scaleYPropertyKey;
+            case ComponentProperty::Opacity:
+                return WorldTransformComponentBase::opacityPropertyKey;
+            case ComponentProperty::BlendMode:
+                return DrawableBase::blendModeValuePropertyKey;
+            case ComponentProperty::Color:
+                return SolidColorBase::colorValuePropertyKey;
+            case ComponentProperty::FillRule:
+                return FillBase::fillRulePropertyKey;
+            case ComponentProperty::IsVisible:
+                return ShapePaintBase::isVisiblePropertyKey;
+            case ComponentProperty::Thickness:
+                return StrokeBase::thicknessPropertyKey;
+            case ComponentProperty::Cap:
+                return StrokeBase::capPropertyKey;
+            case ComponentProperty::Join:
+                return StrokeBase::joinPropertyKey;
+            case ComponentProperty::Length:
+                return BoneBase::lengthPropertyKey;
+            case ComponentProperty::Width:
+                return TextBase::widthPropertyKey;
+            case ComponentProperty::Height:
+                return TextBase::heightPropertyKey;
+        }
+
+        return 0;
+    }
+
+    ComponentKind rive_rs_component_kind(const Core* component)
+    {
+        switch (component->coreType())
+        {
+            case NodeBase::typeKey:
+                return ComponentKind::Node;
+            case ShapeBase::typeKey:
+                return ComponentKind::Shape;
+            case FillBase::typeKey:
+                return ComponentKind::Fill;
+            case StrokeBase::typeKey:
+                return ComponentKind::Stroke;
+            case SolidColorBase::typeKey:
+                return ComponentKind::SolidColor;
+            case BoneBase::typeKey:
+            case RootBoneBase::typeKey:
+                return ComponentKind::Bone;
+            case ImageBase::typeKey:
+                return ComponentKind::Image;
+            case TextBase::typeKey:
+                return ComponentKind::Text;
+            case TextValueRunBase::typeKey:
+                return ComponentKind::TextValueRun;
+            case EventBase::typeKey:
+                return ComponentKind::Event;
+            case NestedArtboardBase::typeKey:
+                return ComponentKind::NestedArtboard;
+            default:
+                return ComponentKind::Other;
+        }
+    }
+
+    // Unlike `rive_rs_component_kind`, this also matches sub-types, e.g. a
+    // shape is a node.
+    bool rive_rs_component_is(const Core* component, ComponentKind kind)
+    {
+        return kind != ComponentKind::Other &&
+               component->isTypeOf(rive_rs_component_kind_type_key(kind));
+    }
+
+    float rive_rs_component_get_f32(Core* component, ComponentProperty property)
+    {
+        return CoreRegistry::getDouble(component,
+                                       rive_rs_component_property_key(property));
+    }
+
+    void rive_rs_component_set_f32(Core* component,
+                                   ComponentProperty property,
+                                   float val)
+    {
+        CoreRegistry::setDouble(component,
+                                rive_rs_component_property_key(property),
+                                val);
+    }
+
+    uint32_t rive_rs_component_get_u32(Core* component,
+                                       ComponentProperty property)
+    {
+        return CoreRegistry::getUint(component,
+                                     rive_rs_component_property_key(property));
+    }
+
+    void rive_rs_component_set_u32(Core* component,
+                                   ComponentProperty property,
+                                   uint32_t val)
+    {
+        CoreRegistry::setUint(component,
+                              rive_rs_component_property_key(property),
+                              val);
+    }
+
+    bool rive_rs_component_get_bool(Core* component, ComponentProperty property)
+    {
+        return CoreRegistry::getBool(component,
+                                     rive_rs_component_property_key(property));
+    }
+
+    void rive_rs_component_set_bool(Core* component,
+                                    ComponentProperty property,
+                                    bool val)
+    {
+        CoreRegistry::setBool(component,
+                              rive_rs_component_property_key(property),
+                              val);
+    }
+
+    ColorInt rive_rs_component_get_color(Core* component,
+                                         ComponentProperty property)
+    {
+        return CoreRegistry::getColor(component,
+                                      rive_rs_component_property_key(property));
+    }
+
+    void rive_rs_component_set_color(Core* component,
+                                     ComponentProperty property,
+                                     ColorInt val)
+    {
+        CoreRegistry::setColor(component,
+                               rive_rs_component_property_key(property),
+                               val);
+    }
+
     void rive_rs_component_name(const Component* component,
                                 const char** data,
                                 size_t* len)
@@ -569,6 +1340,61 @@ extern "C"
         text_value_run->text({data, len});
     }
 
+    NestedArtboard* rive_rs_artboard_nested_artboard_at_path(
+        ArtboardInstance* artboard_instance,
+        const char* path,
+        size_t len)
+    {
+        return artboard_instance->nestedArtboardAtPath({path, len});
+    }
+
+    ArtboardInstance* rive_rs_nested_artboard_artboard(
+        NestedArtboard* nested_artboard)
+    {
+        return nested_artboard->artboardInstance();
+    }
+
+    SMIBool* rive_rs_nested_artboard_get_bool(NestedArtboard* nested_artboard,
+                                              const char* name,
+                                              size_t len)
+    {
+        auto input = nested_artboard->input({name, len});
+        if (input == nullptr || !input->input()->is<StateMachineBool>())
+        {
+            return nullptr;
+        }
+
+        return static_cast<SMIBool*>(input);
+    }
+
+    SMINumber* rive_rs_nested_artboard_get_number(
+        NestedArtboard* nested_artboard,
+        const char* name,
+        size_t len)
+    {
+        auto input = nested_artboard->input({name, len});
+        if (input == nullptr || !input->input()->is<StateMachineNumber>())
+        {
+            return nullptr;
+        }
+
+        return static_cast<SMINumber*>(input);
+    }
+
+    SMITrigger* rive_rs_nested_artboard_get_trigger(
+        NestedArtboard* nested_artboard,
+        const char* name,
+        size_t len)
+    {
+        auto input = nested_artboard->input({name, len});
+        if (input == nullptr || !input->input()->is<StateMachineTrigger>())
+        {
+            return nullptr;
+        }
+
+        return static_cast<SMITrigger*>(input);
+    }
+
     void rive_rs_instantiate_linear_animation(
         ArtboardInstance* artboard_instance,
         const size_t* index,
@@ -863,6 +1689,127 @@ extern "C"
 
     void rive_rs_trigger_fire(SMITrigger* trigger) { trigger->fire(); }
 
+    size_t rive_rs_state_machine_layer_count(
+        const StateMachineInstance* state_machine_instance)
+    {
+        return state_machine_instance->stateMachine()->layerCount();
+    }
+
+    void rive_rs_state_machine_layer_name(
+        const StateMachineInstance* state_machine_instance,
+        size_t index,
+        const char** data,
+        size_t* len)
+    {
+        auto layer = state_machine_instance->stateMachine()->layer(index);
+        *data = layer->name().data();
+        *len = layer->name().size();
+    }
+
+    const LayerState* rive_rs_state_machine_layer_entry_state(
+        const StateMachineInstance* state_machine_instance,
+        size_t index)
+    {
+        return state_machine_instance->stateMachine()
+            ->layer(index)
+            ->entryState();
+    }
+
+    const LayerState* rive_rs_state_machine_layer_current_state(
+        StateMachineInstance* state_machine_instance,
+        size_t index)
+    {
+        return state_machine_instance->layerState(index);
+    }
+
+    StateKind rive_rs_layer_state_kind(const LayerState* state)
+    {
+        if (state->is<EntryState>())
+        {
+            return StateKind::Entry;
+        }
+        if (state->is<ExitState>())
+        {
+            return StateKind::Exit;
+        }
+        if (state->is<AnyState>())
+        {
+            return StateKind::Any;
+        }
+        if (state->is<AnimationState>())
+        {
+            return StateKind::Animation;
+        }
+        if (state->is<BlendState>())
+        {
+            return StateKind::Blend;
+        }
+
+        return StateKind::Unknown;
+    }
+
+    void rive_rs_layer_state_name(const LayerState* state,
+                                  const char** data,
+                                  size_t* len)
+    {
+        if (state->is<AnimationState>())
+        {
+            auto animation = state->as<AnimationState>()->animation();
+            if (animation != nullptr)
+            {
+                *data = animation->name().data();
+                *len = animation->name().size();
+            }
+        }
+    }
+
+    bool rive_rs_layer_state_plays(
+        const LayerState* state,
+        const LinearAnimationInstance* linear_animation)
+    {
+        auto animation = linear_animation->animation();
+
+        if (state->is<AnimationState>())
+        {
+            return state->as<AnimationState>()->animation() == animation;
+        }
+        if (state->is<BlendState>())
+        {
+            for (auto blend_animation :
+                 state->as<BlendState>()->animations())
+            {
+                if (blend_animation->animation() == animation)
+                {
+                    return true;
+                }
+            }
+        }
+
+        return false;
+    }
+
+    size_t rive_rs_state_machine_playing_animation_count(
+        const StateMachineInstance* state_machine_instance)
+    {
+        return state_machine_instance->currentAnimationCount();
+    }
+
+    const LinearAnimationInstance* rive_rs_state_machine_playing_animation_at(
+        const StateMachineInstance* state_machine_instance,
+        size_t index)
+    {
+        return state_machine_instance->currentAnimationByIndex(index);
+    }
+
+    void rive_rs_linear_animation_name(
+        const LinearAnimationInstance* linear_animation,
+        const char** data,
+        size_t* len)
+    {
+        *data = linear_animation->animation()->name().data();
+        *len = linear_animation->animation()->name().size();
+    }
+
     void rive_rs_scene_release(const Scene* scene)
     {
         std::unique_ptr<Scene> val(std::move(const_cast<Scene*>(scene)));
@@ -904,31 +1851,59 @@ extern "C"
         scene->draw(&rust_renderer);
     }
 
-    void rive_rs_scene_pointer_down(Scene* scene, float x, float y)
+    bool rive_rs_state_machine_hit_test(
+        const StateMachineInstance* state_machine_instance,
+        float x,
+        float y)
+    {
+        return state_machine_instance->hitTest({x, y});
+    }
+
+    HitResult rive_rs_scene_pointer_down(Scene* scene,
+                                         int pointer_id,
+                                         float x,
+                                         float y)
     {
-        scene->pointerDown({x, y});
+        return scene->pointerDown({x, y}, pointer_id);
     }
 
-    void rive_rs_scene_pointer_move(Scene* scene, float x, float y)
+    HitResult rive_rs_scene_pointer_move(Scene* scene,
+                                         int pointer_id,
+                                         float x,
+                                         float y)
     {
-        scene->pointerMove({x, y});
+        return scene->pointerMove({x, y}, 0.0f, pointer_id);
     }
 
-    void rive_rs_scene_pointer_up(Scene* scene, float x, float y)
+    HitResult rive_rs_scene_pointer_up(Scene* scene,
+                                       int pointer_id,
+                                       float x,
+                                       float y)
     {
-        scene->pointerUp({x, y});
+        return scene->pointerUp({x, y}, pointer_id);
+    }
+
+    HitResult rive_rs_scene_pointer_exit(Scene* scene,
+                                         int pointer_id,
+                                         float x,
+                                         float y)
+    {
+        return scene->pointerExit({x, y}, pointer_id);
     }
 
     void rive_rs_artboard_instance_transforms(
-        const ArtboardInstance* artboard_instance,
+        ArtboardInstance* artboard_instance,
         uint32_t width,
         uint32_t height,
+        Fit fit,
+        float alignment_x,
+        float alignment_y,
         float* view_transform,
         float* inverse_view_transform)
     {
         auto view_transform_mat =
-            rive::computeAlignment(rive::Fit::contain,
-                                   rive::Alignment::center,
+            rive::computeAlignment(fit,
+                                   rive::Alignment(alignment_x, alignment_y),
                                    rive::AABB(0, 0, width, height),
                                    artboard_instance->bounds());
         auto inverse_view_transform_mat = view_transform_mat.invertOrIdentity();
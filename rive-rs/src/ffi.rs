@@ -5,13 +5,16 @@ use core::{
 };
 
 use crate::{
+    artboard::components::ComponentKind,
+    file::assets::{Asset, AssetLoader, AssetType},
     linear_animation::Loop,
     path::{self, FillRule, Point, Verb},
     renderer::{
-        BlendMode, Buffer, BufferFlags, BufferType, Color, Gradient, Image, Paint, PaintStyle,
-        Path, Renderer, StrokeCap, StrokeJoin,
+        BlendMode, Buffer, BufferFlags, BufferType, Color, Gradient, Image, ImageSampler, Paint,
+        PaintStyle, Path, Renderer, StrokeCap, StrokeJoin,
     },
-    state_machine,
+    scene::{Fit, HitResult},
+    state_machine::{self, StateKind},
 };
 
 #[repr(C)]
@@ -32,8 +35,9 @@ pub struct Command {
 pub enum Commands {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
-pub enum FileResult {
+#[derive(Clone, Copy, Debug, Default)]
+pub enum FileResultKind {
+    #[default]
     Success,
     #[allow(dead_code)]
     UnsupportedVersion,
@@ -41,21 +45,75 @@ pub enum FileResult {
     Malformed,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileResult {
+    pub kind: FileResultKind,
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub supported_major_version: u32,
+    pub supported_minor_version: u32,
+    pub offset: usize,
+    pub has_offset: bool,
+    pub type_key: u16,
+    pub has_type_key: bool,
+}
+
 #[derive(Clone, Copy)]
 pub enum Factory {}
 
 #[derive(Clone, Copy)]
 pub enum File {}
 
+#[derive(Clone, Copy)]
+pub enum FileAsset {}
+
 #[derive(Clone, Copy)]
 pub enum Artboard {}
 
+#[derive(Clone, Copy)]
+pub enum ArtboardDescriptor {}
+
+#[derive(Clone, Copy)]
+pub enum LinearAnimationDescriptor {}
+
+#[derive(Clone, Copy)]
+pub enum StateMachineDescriptor {}
+
+#[derive(Clone, Copy)]
+pub enum InputDescriptor {}
+
 #[derive(Clone, Copy)]
 pub enum Component {}
 
 #[derive(Clone, Copy)]
 pub enum TextValueRun {}
 
+#[derive(Clone, Copy)]
+pub enum NestedArtboard {}
+
+#[allow(dead_code)]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug)]
+pub enum ComponentProperty {
+    X,
+    Y,
+    Rotation,
+    ScaleX,
+    ScaleY,
+    Opacity,
+    BlendMode,
+    Color,
+    FillRule,
+    IsVisible,
+    Thickness,
+    Cap,
+    Join,
+    Length,
+    Width,
+    Height,
+}
+
 #[derive(Clone, Copy)]
 pub enum LinearAnimation {}
 
@@ -97,6 +155,9 @@ pub enum InputTag {
     Trigger,
 }
 
+#[derive(Clone, Copy)]
+pub enum LayerState {}
+
 #[derive(Clone, Copy)]
 pub enum Bool {}
 
@@ -145,6 +206,24 @@ unsafe extern "C" fn rive_rs_insert_property(
     }
 }
 
+#[no_mangle]
+unsafe extern "C" fn rive_rs_asset_loader_load(
+    asset_loader: *mut Box<dyn AssetLoader>,
+    asset: *mut FileAsset,
+    in_band_data: *const u8,
+    in_band_len: usize,
+    factory: *mut Factory,
+) -> bool {
+    let asset_loader = &mut *asset_loader;
+    let in_band_bytes = if in_band_data.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(in_band_data, in_band_len)
+    };
+
+    asset_loader.load(&mut Asset::new(asset, factory, in_band_bytes, None))
+}
+
 #[repr(C)]
 pub struct RendererEntries<R: Renderer> {
     buffer_new: unsafe extern "C" fn(BufferType, BufferFlags, usize) -> *mut R::Buffer,
@@ -190,11 +269,17 @@ pub struct RendererEntries<R: Renderer> {
     renderer_transform: unsafe extern "C" fn(*mut R, transform: *const [f32; 6]),
     renderer_set_clip: unsafe extern "C" fn(*mut R, path: *const R::Path),
     renderer_draw_path: unsafe extern "C" fn(*mut R, path: *const R::Path, paint: *const R::Paint),
-    renderer_draw_image:
-        unsafe extern "C" fn(*mut R, image: *const R::Image, blend_mode: BlendMode, opacity: f32),
+    renderer_draw_image: unsafe extern "C" fn(
+        *mut R,
+        image: *const R::Image,
+        sampler: ImageSampler,
+        blend_mode: BlendMode,
+        opacity: f32,
+    ),
     renderer_draw_image_mesh: unsafe extern "C" fn(
         *mut R,
         image: *const R::Image,
+        sampler: ImageSampler,
         vertices: *const R::Buffer,
         uvs: *const R::Buffer,
         indices: *const R::Buffer,
@@ -422,22 +507,26 @@ impl<R: Renderer> RendererEntries<R> {
         unsafe extern "C" fn renderer_draw_image<R: Renderer>(
             renderer: *mut R,
             image: *const R::Image,
+            sampler: ImageSampler,
             blend_mode: BlendMode,
             opacity: f32,
         ) {
-            (*renderer).draw_image(&*image, blend_mode, opacity);
+            (*renderer).draw_image(&*image, sampler, blend_mode, opacity);
         }
 
         unsafe extern "C" fn renderer_draw_image_mesh<R: Renderer>(
             renderer: *mut R,
             image: *const R::Image,
+            sampler: ImageSampler,
             vertices: *const R::Buffer,
             uvs: *const R::Buffer,
             indices: *const R::Buffer,
             blend_mode: BlendMode,
             opacity: f32,
         ) {
-            (*renderer).draw_image_mesh(&*image, &*vertices, &*uvs, &*indices, blend_mode, opacity);
+            (*renderer).draw_image_mesh(
+                &*image, sampler, &*vertices, &*uvs, &*indices, blend_mode, opacity,
+            );
         }
 
         &Self {
@@ -487,10 +576,90 @@ extern "C" {
         data: *const u8,
         len: usize,
         entries: *const (),
+        asset_loader: *mut (),
         result: *mut FileResult,
         factory: *mut *mut Factory,
     ) -> *const File;
     pub fn rive_rs_file_release(file: *const File, factory: *mut Factory);
+    pub fn rive_rs_file_load_asset(
+        file: *const File,
+        id: u32,
+        data: *const u8,
+        len: usize,
+        factory: *mut Factory,
+    ) -> bool;
+    pub fn rive_rs_file_asset_count(file: *const File) -> usize;
+    pub fn rive_rs_file_get_asset(file: *const File, index: usize) -> *mut FileAsset;
+    pub fn rive_rs_file_asset_id(asset: *mut FileAsset) -> u32;
+    pub fn rive_rs_file_asset_name(asset: *mut FileAsset, data: *mut *const u8, len: *mut usize);
+    #[allow(improper_ctypes)]
+    pub fn rive_rs_file_asset_unique_filename(asset: *mut FileAsset, string: *mut String);
+    #[allow(improper_ctypes)]
+    pub fn rive_rs_file_asset_cdn_uuid(asset: *mut FileAsset, string: *mut String);
+    pub fn rive_rs_file_asset_type(asset: *mut FileAsset) -> AssetType;
+    pub fn rive_rs_file_asset_decode(
+        asset: *mut FileAsset,
+        data: *const u8,
+        len: usize,
+        factory: *mut Factory,
+    ) -> bool;
+    pub fn rive_rs_file_artboard_count(file: *const File) -> usize;
+    pub fn rive_rs_file_artboard_at(file: *const File, index: usize) -> *const ArtboardDescriptor;
+    pub fn rive_rs_artboard_descriptor_width(artboard: *const ArtboardDescriptor) -> f32;
+    pub fn rive_rs_artboard_descriptor_height(artboard: *const ArtboardDescriptor) -> f32;
+    pub fn rive_rs_artboard_descriptor_origin(
+        artboard: *const ArtboardDescriptor,
+        x: *mut f32,
+        y: *mut f32,
+    );
+    pub fn rive_rs_artboard_descriptor_animation_count(
+        artboard: *const ArtboardDescriptor,
+    ) -> usize;
+    pub fn rive_rs_artboard_descriptor_animation_at(
+        artboard: *const ArtboardDescriptor,
+        index: usize,
+    ) -> *const LinearAnimationDescriptor;
+    pub fn rive_rs_artboard_descriptor_state_machine_count(
+        artboard: *const ArtboardDescriptor,
+    ) -> usize;
+    pub fn rive_rs_artboard_descriptor_state_machine_at(
+        artboard: *const ArtboardDescriptor,
+        index: usize,
+    ) -> *const StateMachineDescriptor;
+    pub fn rive_rs_linear_animation_descriptor_name(
+        linear_animation: *const LinearAnimationDescriptor,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
+    pub fn rive_rs_linear_animation_descriptor_duration(
+        linear_animation: *const LinearAnimationDescriptor,
+    ) -> f32;
+    pub fn rive_rs_linear_animation_descriptor_fps(
+        linear_animation: *const LinearAnimationDescriptor,
+    ) -> u32;
+    pub fn rive_rs_linear_animation_descriptor_loop(
+        linear_animation: *const LinearAnimationDescriptor,
+    ) -> Loop;
+    pub fn rive_rs_state_machine_descriptor_name(
+        state_machine: *const StateMachineDescriptor,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
+    pub fn rive_rs_state_machine_descriptor_input_count(
+        state_machine: *const StateMachineDescriptor,
+    ) -> usize;
+    pub fn rive_rs_state_machine_descriptor_input_at(
+        state_machine: *const StateMachineDescriptor,
+        index: usize,
+    ) -> *const InputDescriptor;
+    pub fn rive_rs_input_descriptor_name(
+        input: *const InputDescriptor,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
+    pub fn rive_rs_input_descriptor_tag(input: *const InputDescriptor) -> InputTag;
+    pub fn rive_rs_input_descriptor_bool_default(input: *const InputDescriptor) -> bool;
+    pub fn rive_rs_input_descriptor_number_default(input: *const InputDescriptor) -> f32;
     pub fn rive_rs_instantiate_artboard(
         file: *const File,
         index: Option<NonNull<usize>>,
@@ -508,7 +677,61 @@ extern "C" {
         artboard_instance: *mut Artboard,
         index: usize,
     ) -> *mut Component;
+    pub fn rive_rs_component_hit_test(component: *mut Component, x: f32, y: f32) -> bool;
+    pub fn rive_rs_artboard_advance(artboard_instance: *mut Artboard, elapsed: f32) -> bool;
+    pub fn rive_rs_artboard_draw(
+        artboard_instance: *mut Artboard,
+        renderer: *mut (),
+        entries: *const (),
+    );
+    pub fn rive_rs_artboard_bounds(
+        artboard_instance: *mut Artboard,
+        min_x: *mut f32,
+        min_y: *mut f32,
+        max_x: *mut f32,
+        max_y: *mut f32,
+    );
+    pub fn rive_rs_artboard_width(artboard_instance: *mut Artboard) -> f32;
+    pub fn rive_rs_artboard_set_width(artboard_instance: *mut Artboard, width: f32);
+    pub fn rive_rs_artboard_height(artboard_instance: *mut Artboard) -> f32;
+    pub fn rive_rs_artboard_set_height(artboard_instance: *mut Artboard, height: f32);
+    pub fn rive_rs_artboard_origin(artboard_instance: *mut Artboard, x: *mut f32, y: *mut f32);
+    pub fn rive_rs_artboard_set_origin(artboard_instance: *mut Artboard, x: f32, y: f32);
     pub fn rive_rs_component_type_id(component: *const Component) -> u16;
+    pub fn rive_rs_component_kind(component: *const Component) -> ComponentKind;
+    pub fn rive_rs_component_is(component: *const Component, kind: ComponentKind) -> bool;
+    pub fn rive_rs_component_get_f32(component: *mut Component, property: ComponentProperty)
+        -> f32;
+    pub fn rive_rs_component_set_f32(
+        component: *mut Component,
+        property: ComponentProperty,
+        val: f32,
+    );
+    pub fn rive_rs_component_get_u32(component: *mut Component, property: ComponentProperty)
+        -> u32;
+    pub fn rive_rs_component_set_u32(
+        component: *mut Component,
+        property: ComponentProperty,
+        val: u32,
+    );
+    pub fn rive_rs_component_get_bool(
+        component: *mut Component,
+        property: ComponentProperty,
+    ) -> bool;
+    pub fn rive_rs_component_set_bool(
+        component: *mut Component,
+        property: ComponentProperty,
+        val: bool,
+    );
+    pub fn rive_rs_component_get_color(
+        component: *mut Component,
+        property: ComponentProperty,
+    ) -> Color;
+    pub fn rive_rs_component_set_color(
+        component: *mut Component,
+        property: ComponentProperty,
+        val: Color,
+    );
     pub fn rive_rs_component_name(
         component: *const Component,
         data: *mut *const u8,
@@ -524,6 +747,27 @@ extern "C" {
         data: *const u8,
         len: usize,
     );
+    pub fn rive_rs_artboard_nested_artboard_at_path(
+        artboard: *mut Artboard,
+        path: *const u8,
+        len: usize,
+    ) -> *mut NestedArtboard;
+    pub fn rive_rs_nested_artboard_artboard(nested_artboard: *mut NestedArtboard) -> *mut Artboard;
+    pub fn rive_rs_nested_artboard_get_bool(
+        nested_artboard: *mut NestedArtboard,
+        name: *const u8,
+        len: usize,
+    ) -> *mut Bool;
+    pub fn rive_rs_nested_artboard_get_number(
+        nested_artboard: *mut NestedArtboard,
+        name: *const u8,
+        len: usize,
+    ) -> *mut Number;
+    pub fn rive_rs_nested_artboard_get_trigger(
+        nested_artboard: *mut NestedArtboard,
+        name: *const u8,
+        len: usize,
+    ) -> *mut Trigger;
     pub fn rive_rs_instantiate_linear_animation(
         artboard: *mut Artboard,
         index: Option<NonNull<usize>>,
@@ -603,10 +847,49 @@ extern "C" {
     pub fn rive_rs_number_get(number: *mut Number) -> f32;
     pub fn rive_rs_number_set(number: *mut Number, val: f32);
     pub fn rive_rs_trigger_fire(trigger: *mut Trigger);
+    pub fn rive_rs_state_machine_layer_count(state_machine: *mut StateMachine) -> usize;
+    pub fn rive_rs_state_machine_layer_name(
+        state_machine: *mut StateMachine,
+        index: usize,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
+    pub fn rive_rs_state_machine_layer_entry_state(
+        state_machine: *mut StateMachine,
+        index: usize,
+    ) -> *const LayerState;
+    pub fn rive_rs_state_machine_layer_current_state(
+        state_machine: *mut StateMachine,
+        index: usize,
+    ) -> *const LayerState;
+    pub fn rive_rs_layer_state_kind(state: *const LayerState) -> StateKind;
+    pub fn rive_rs_layer_state_name(
+        state: *const LayerState,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
+    pub fn rive_rs_layer_state_plays(
+        state: *const LayerState,
+        linear_animation: *const LinearAnimation,
+    ) -> bool;
+    pub fn rive_rs_state_machine_playing_animation_count(state_machine: *mut StateMachine)
+        -> usize;
+    pub fn rive_rs_state_machine_playing_animation_at(
+        state_machine: *mut StateMachine,
+        index: usize,
+    ) -> *const LinearAnimation;
+    pub fn rive_rs_linear_animation_name(
+        linear_animation: *const LinearAnimation,
+        data: *mut *const u8,
+        len: *mut usize,
+    );
     pub fn rive_rs_artboard_instance_transforms(
         artboard_instance: *mut Artboard,
         width: u32,
         height: u32,
+        fit: Fit,
+        alignment_x: f32,
+        alignment_y: f32,
         view_transform: *mut f32,
         inverse_view_transform: *mut f32,
     );
@@ -619,7 +902,30 @@ extern "C" {
     pub fn rive_rs_scene_duration(scene: *mut Scene) -> f32;
     pub fn rive_rs_scene_advance_and_apply(scene: *mut Scene, elapsed: f32) -> bool;
     pub fn rive_rs_scene_draw(scene: *mut Scene, renderer: *mut (), entries: *const ());
-    pub fn rive_rs_scene_pointer_down(scene: *mut Scene, x: f32, y: f32);
-    pub fn rive_rs_scene_pointer_move(scene: *mut Scene, x: f32, y: f32);
-    pub fn rive_rs_scene_pointer_up(scene: *mut Scene, x: f32, y: f32);
+    pub fn rive_rs_state_machine_hit_test(state_machine: *mut StateMachine, x: f32, y: f32)
+        -> bool;
+    pub fn rive_rs_scene_pointer_down(
+        scene: *mut Scene,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+    ) -> HitResult;
+    pub fn rive_rs_scene_pointer_move(
+        scene: *mut Scene,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+    ) -> HitResult;
+    pub fn rive_rs_scene_pointer_up(
+        scene: *mut Scene,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+    ) -> HitResult;
+    pub fn rive_rs_scene_pointer_exit(
+        scene: *mut Scene,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+    ) -> HitResult;
 }
@@ -0,0 +1,233 @@
+use alloc::string::String;
+use core::{ops::Deref, ptr, slice, str};
+
+use crate::{
+    ffi,
+    file::AssetLock,
+    raw_iter::{impl_iter, Raw},
+};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AssetType {
+    Image,
+    Font,
+    Audio,
+    Unknown,
+}
+
+/// An asset referenced by a Rive file, either while it is being imported or, through
+/// [`File::assets`](crate::File#method.assets), once it is live.
+pub struct Asset<'a> {
+    raw_asset: *mut ffi::FileAsset,
+    raw_factory: *mut ffi::Factory,
+    in_band_bytes: &'a [u8],
+    /// Lock of the live file, `None` while it is being imported.
+    assets_lock: Option<&'a AssetLock>,
+}
+
+impl<'a> Asset<'a> {
+    pub(crate) fn new(
+        raw_asset: *mut ffi::FileAsset,
+        raw_factory: *mut ffi::Factory,
+        in_band_bytes: &'a [u8],
+        assets_lock: Option<&'a AssetLock>,
+    ) -> Self {
+        Self {
+            raw_asset,
+            raw_factory,
+            in_band_bytes,
+            assets_lock,
+        }
+    }
+
+    /// Identifier of the asset, unique within its file. It can be used to load the asset at a
+    /// later time with [`File::load_asset`](crate::File#method.load_asset).
+    #[inline]
+    pub fn id(&self) -> u32 {
+        unsafe { ffi::rive_rs_file_asset_id(self.raw_asset) }
+    }
+
+    pub fn name(&self) -> &str {
+        let mut data = ptr::null();
+        let mut len = 0;
+
+        let bytes = unsafe {
+            ffi::rive_rs_file_asset_name(
+                self.raw_asset,
+                &mut data as *mut *const u8,
+                &mut len as *mut usize,
+            );
+            slice::from_raw_parts(data, len)
+        };
+
+        str::from_utf8(bytes).expect("asset name is invalid UTF-8")
+    }
+
+    /// Name of the asset, suffixed with its identifier and extension, e.g. `"avatar-42.png"`.
+    pub fn unique_filename(&self) -> String {
+        let mut unique_filename = String::new();
+
+        unsafe {
+            ffi::rive_rs_file_asset_unique_filename(
+                self.raw_asset,
+                &mut unique_filename as *mut String,
+            );
+        }
+
+        unique_filename
+    }
+
+    /// UUID of the asset on the Rive CDN, or an empty string for assets which are not hosted.
+    pub fn cdn_uuid(&self) -> String {
+        let mut cdn_uuid = String::new();
+
+        unsafe {
+            ffi::rive_rs_file_asset_cdn_uuid(self.raw_asset, &mut cdn_uuid as *mut String);
+        }
+
+        cdn_uuid
+    }
+
+    #[inline]
+    pub fn r#type(&self) -> AssetType {
+        unsafe { ffi::rive_rs_file_asset_type(self.raw_asset) }
+    }
+
+    /// Bytes embedded in the file for this asset, empty for referenced and hosted assets.
+    #[inline]
+    pub fn in_band_bytes(&self) -> &'a [u8] {
+        self.in_band_bytes
+    }
+
+    /// Decodes `bytes` into the asset, e.g. the PNG data of an image or the TTF data of a font.
+    /// Returns `false` if the bytes could not be decoded.
+    ///
+    /// Artboards of a live file that are being drawn or advanced on other threads finish doing so
+    /// before the asset changes.
+    pub fn decode(&mut self, bytes: &[u8]) -> bool {
+        let decode = || unsafe {
+            ffi::rive_rs_file_asset_decode(
+                self.raw_asset,
+                bytes.as_ptr(),
+                bytes.len(),
+                self.raw_factory,
+            )
+        };
+
+        match self.assets_lock {
+            Some(assets_lock) => assets_lock.write(decode),
+            None => decode(),
+        }
+    }
+}
+
+/// An [`Asset`] containing an image.
+pub struct ImageAsset<'a> {
+    asset: Asset<'a>,
+}
+
+impl ImageAsset<'_> {
+    /// Replaces the image with the one encoded in `bytes`, which is decoded by the file's
+    /// [`Renderer`](crate::renderer::Renderer). All artboards instantiated from the file draw the
+    /// new image from their next frame on. Returns `false` if the image could not be decoded.
+    #[inline]
+    pub fn set_image(&mut self, bytes: &[u8]) -> bool {
+        self.asset.decode(bytes)
+    }
+}
+
+impl<'a> Deref for ImageAsset<'a> {
+    type Target = Asset<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.asset
+    }
+}
+
+impl<'a> TryFrom<Asset<'a>> for ImageAsset<'a> {
+    type Error = ();
+
+    fn try_from(asset: Asset<'a>) -> Result<Self, Self::Error> {
+        (asset.r#type() == AssetType::Image)
+            .then_some(Self { asset })
+            .ok_or(())
+    }
+}
+
+/// An [`Asset`] containing a font.
+pub struct FontAsset<'a> {
+    asset: Asset<'a>,
+}
+
+impl FontAsset<'_> {
+    /// Replaces the font with the one in `bytes`. All artboards instantiated from the file
+    /// reshape their text with the new font from their next frame on. Returns `false` if the font
+    /// could not be decoded.
+    #[inline]
+    pub fn set_font(&mut self, bytes: &[u8]) -> bool {
+        self.asset.decode(bytes)
+    }
+}
+
+impl<'a> Deref for FontAsset<'a> {
+    type Target = Asset<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.asset
+    }
+}
+
+impl<'a> TryFrom<Asset<'a>> for FontAsset<'a> {
+    type Error = ();
+
+    fn try_from(asset: Asset<'a>) -> Result<Self, Self::Error> {
+        (asset.r#type() == AssetType::Font)
+            .then_some(Self { asset })
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawFile {
+    pub raw_file: *const ffi::File,
+    pub raw_factory: *mut ffi::Factory,
+    pub assets_lock: *const AssetLock,
+}
+
+impl Raw for RawFile {
+    type Item<'a> = Asset<'a>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_file_asset_count(self.raw_file) }
+    }
+
+    unsafe fn get<'a>(self, index: usize) -> Self::Item<'a> {
+        Asset::new(
+            ffi::rive_rs_file_get_asset(self.raw_file, index),
+            self.raw_factory,
+            &[],
+            Some(&*self.assets_lock),
+        )
+    }
+}
+
+impl_iter!(Assets, Asset, RawFile, 'a);
+
+/// Loads the contents of the assets of a Rive file out of band.
+pub trait AssetLoader: Send + Sync {
+    /// Called for every asset while the file is imported. Returns `true` if the asset has been
+    /// taken care of, either by calling [`Asset::decode`] right away or by loading it later with
+    /// [`File::load_asset`](crate::File#method.load_asset), or `false` to let the runtime fall
+    /// back to the bytes embedded in the file.
+    fn load(&mut self, asset: &mut Asset<'_>) -> bool;
+}
+
+impl<F: FnMut(&mut Asset<'_>) -> bool + Send + Sync> AssetLoader for F {
+    #[inline]
+    fn load(&mut self, asset: &mut Asset<'_>) -> bool {
+        self(asset)
+    }
+}
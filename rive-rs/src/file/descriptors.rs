@@ -0,0 +1,312 @@
+use core::{fmt, marker::PhantomData, ptr, slice, str, time::Duration};
+
+use crate::{
+    ffi,
+    linear_animation::Loop,
+    path::Point,
+    raw_iter::{impl_iter, Raw},
+    state_machine::InputValue,
+};
+
+unsafe fn name<'f>(get_name: impl FnOnce(*mut *const u8, *mut usize)) -> &'f str {
+    let mut data = ptr::null();
+    let mut len = 0;
+
+    let bytes = unsafe {
+        get_name(&mut data as *mut *const u8, &mut len as *mut usize);
+        slice::from_raw_parts(data, len)
+    };
+
+    str::from_utf8(bytes).expect("name is invalid UTF-8")
+}
+
+/// Describes an artboard of a [`File`](crate::File) without instantiating it.
+pub struct ArtboardDescriptor<'f> {
+    raw_artboard: *const ffi::ArtboardDescriptor,
+    index: usize,
+    _phantom: PhantomData<&'f ()>,
+}
+
+impl<'f> ArtboardDescriptor<'f> {
+    pub fn name(&self) -> &'f str {
+        unsafe {
+            name(|data, len| {
+                ffi::rive_rs_component_name(self.raw_artboard as *const ffi::Component, data, len)
+            })
+        }
+    }
+
+    /// Index that can be passed to [`Handle::Index`](crate::Handle::Index) to instantiate this
+    /// artboard.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn width(&self) -> f32 {
+        unsafe { ffi::rive_rs_artboard_descriptor_width(self.raw_artboard) }
+    }
+
+    #[inline]
+    pub fn height(&self) -> f32 {
+        unsafe { ffi::rive_rs_artboard_descriptor_height(self.raw_artboard) }
+    }
+
+    /// Origin of the artboard, relative to its size.
+    #[inline]
+    pub fn origin(&self) -> Point {
+        let mut origin = Point { x: 0.0, y: 0.0 };
+
+        unsafe {
+            ffi::rive_rs_artboard_descriptor_origin(
+                self.raw_artboard,
+                &mut origin.x,
+                &mut origin.y,
+            );
+        }
+
+        origin
+    }
+
+    #[inline]
+    pub fn animations(&self) -> LinearAnimationDescriptors<'f> {
+        LinearAnimationDescriptors::new(RawArtboardAnimations(self.raw_artboard))
+    }
+
+    #[inline]
+    pub fn state_machines(&self) -> StateMachineDescriptors<'f> {
+        StateMachineDescriptors::new(RawArtboardStateMachines(self.raw_artboard))
+    }
+}
+
+impl fmt::Debug for ArtboardDescriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArtboardDescriptor")
+            .field("name", &self.name())
+            .field("index", &self.index)
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("origin", &self.origin())
+            .finish()
+    }
+}
+
+/// Describes a linear animation of an artboard without instantiating it.
+pub struct LinearAnimationDescriptor<'f> {
+    raw_linear_animation: *const ffi::LinearAnimationDescriptor,
+    index: usize,
+    _phantom: PhantomData<&'f ()>,
+}
+
+impl<'f> LinearAnimationDescriptor<'f> {
+    pub fn name(&self) -> &'f str {
+        unsafe {
+            name(|data, len| {
+                ffi::rive_rs_linear_animation_descriptor_name(self.raw_linear_animation, data, len)
+            })
+        }
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Duration of the animation, limited to its work area if it has one enabled.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        Duration::try_from_secs_f32(unsafe {
+            ffi::rive_rs_linear_animation_descriptor_duration(self.raw_linear_animation)
+        })
+        .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn fps(&self) -> u32 {
+        unsafe { ffi::rive_rs_linear_animation_descriptor_fps(self.raw_linear_animation) }
+    }
+
+    #[inline]
+    pub fn r#loop(&self) -> Loop {
+        unsafe { ffi::rive_rs_linear_animation_descriptor_loop(self.raw_linear_animation) }
+    }
+}
+
+impl fmt::Debug for LinearAnimationDescriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinearAnimationDescriptor")
+            .field("name", &self.name())
+            .field("index", &self.index)
+            .field("duration", &self.duration())
+            .field("fps", &self.fps())
+            .field("loop", &self.r#loop())
+            .finish()
+    }
+}
+
+/// Describes a state machine of an artboard without instantiating it.
+pub struct StateMachineDescriptor<'f> {
+    raw_state_machine: *const ffi::StateMachineDescriptor,
+    index: usize,
+    _phantom: PhantomData<&'f ()>,
+}
+
+impl<'f> StateMachineDescriptor<'f> {
+    pub fn name(&self) -> &'f str {
+        unsafe {
+            name(|data, len| {
+                ffi::rive_rs_state_machine_descriptor_name(self.raw_state_machine, data, len)
+            })
+        }
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn inputs(&self) -> InputDescriptors<'f> {
+        InputDescriptors::new(RawStateMachineInputs(self.raw_state_machine))
+    }
+}
+
+impl fmt::Debug for StateMachineDescriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateMachineDescriptor")
+            .field("name", &self.name())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Describes an input of a state machine without instantiating it.
+pub struct InputDescriptor<'f> {
+    raw_input: *const ffi::InputDescriptor,
+    _phantom: PhantomData<&'f ()>,
+}
+
+impl<'f> InputDescriptor<'f> {
+    pub fn name(&self) -> &'f str {
+        unsafe { name(|data, len| ffi::rive_rs_input_descriptor_name(self.raw_input, data, len)) }
+    }
+
+    /// Value of the input when the state machine is instantiated. Its variant gives the kind of
+    /// the input.
+    pub fn default_value(&self) -> InputValue {
+        unsafe {
+            match ffi::rive_rs_input_descriptor_tag(self.raw_input) {
+                ffi::InputTag::Bool => {
+                    InputValue::Bool(ffi::rive_rs_input_descriptor_bool_default(self.raw_input))
+                }
+                ffi::InputTag::Number => {
+                    InputValue::Number(ffi::rive_rs_input_descriptor_number_default(self.raw_input))
+                }
+                ffi::InputTag::Trigger => InputValue::Trigger,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for InputDescriptor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputDescriptor")
+            .field("name", &self.name())
+            .field("default_value", &self.default_value())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawFileArtboards(pub *const ffi::File);
+
+impl Raw for RawFileArtboards {
+    type Item<'f> = ArtboardDescriptor<'f>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_file_artboard_count(self.0) }
+    }
+
+    unsafe fn get<'f>(self, index: usize) -> Self::Item<'f> {
+        ArtboardDescriptor {
+            raw_artboard: ffi::rive_rs_file_artboard_at(self.0, index),
+            index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl_iter!(ArtboardDescriptors, ArtboardDescriptor, RawFileArtboards, 'f);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawArtboardAnimations(*const ffi::ArtboardDescriptor);
+
+impl Raw for RawArtboardAnimations {
+    type Item<'f> = LinearAnimationDescriptor<'f>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_artboard_descriptor_animation_count(self.0) }
+    }
+
+    unsafe fn get<'f>(self, index: usize) -> Self::Item<'f> {
+        LinearAnimationDescriptor {
+            raw_linear_animation: ffi::rive_rs_artboard_descriptor_animation_at(self.0, index),
+            index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl_iter!(
+    LinearAnimationDescriptors,
+    LinearAnimationDescriptor,
+    RawArtboardAnimations,
+    'f
+);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawArtboardStateMachines(*const ffi::ArtboardDescriptor);
+
+impl Raw for RawArtboardStateMachines {
+    type Item<'f> = StateMachineDescriptor<'f>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_artboard_descriptor_state_machine_count(self.0) }
+    }
+
+    unsafe fn get<'f>(self, index: usize) -> Self::Item<'f> {
+        StateMachineDescriptor {
+            raw_state_machine: ffi::rive_rs_artboard_descriptor_state_machine_at(self.0, index),
+            index,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl_iter!(
+    StateMachineDescriptors,
+    StateMachineDescriptor,
+    RawArtboardStateMachines,
+    'f
+);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawStateMachineInputs(*const ffi::StateMachineDescriptor);
+
+impl Raw for RawStateMachineInputs {
+    type Item<'f> = InputDescriptor<'f>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_state_machine_descriptor_input_count(self.0) }
+    }
+
+    unsafe fn get<'f>(self, index: usize) -> Self::Item<'f> {
+        InputDescriptor {
+            raw_input: ffi::rive_rs_state_machine_descriptor_input_at(self.0, index),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl_iter!(InputDescriptors, InputDescriptor, RawStateMachineInputs, 'f);
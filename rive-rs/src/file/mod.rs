@@ -0,0 +1,347 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt, hint,
+    marker::PhantomData,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{
+    ffi::{self},
+    renderer::Renderer,
+};
+
+pub mod assets;
+pub mod descriptors;
+
+use self::{
+    assets::{AssetLoader, Assets},
+    descriptors::ArtboardDescriptors,
+};
+
+/// Version of the Rive file format.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+    /// Indicates that the Rive file is not supported by this runtime.
+    UnsupportedVersion {
+        /// Version the file was exported with.
+        file: Version,
+        /// Version this runtime supports. Files need to match its major version.
+        supported: Version,
+    },
+    /// Indicates that the there is a formatting problem in the file itself.
+    Malformed {
+        /// Byte offset of the object that could not be read, if the problem could be located.
+        offset: Option<usize>,
+        /// Type key of the object that could not be read, if it could be read at all.
+        type_key: Option<u16>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnsupportedVersion { file, supported } => write!(
+                f,
+                "unsupported Rive version {file} (runtime supports {}.x, up to {supported})",
+                supported.major,
+            ),
+            Error::Malformed { offset, type_key } => {
+                f.write_str("file is incorrectly encoded")?;
+
+                if let Some(offset) = offset {
+                    write!(f, " at byte {offset}")?;
+                }
+
+                if let Some(type_key) = type_key {
+                    write!(f, " while reading object with type key {type_key}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "software", feature = "svg", feature = "vello"))]
+impl std::error::Error for Error {}
+
+/// Keeps the assets of a file from being swapped while artboards instantiated from it read them.
+///
+/// Draws and advances share the lock, while swapping an asset takes it exclusively and waits for
+/// them to finish. The crate doesn't depend on `std` without a render back-end, so waiting spins.
+/// The lock is only ever held for a single draw, advance or swap.
+#[derive(Debug, Default)]
+pub(crate) struct AssetLock {
+    readers: AtomicUsize,
+    writer: AtomicBool,
+}
+
+impl AssetLock {
+    /// Runs `f` while no asset is being swapped.
+    pub fn read<T>(&self, f: impl FnOnce() -> T) -> T {
+        loop {
+            while self.writer.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+
+            self.readers.fetch_add(1, Ordering::SeqCst);
+
+            // A swap that started in the meantime goes first.
+            if !self.writer.load(Ordering::SeqCst) {
+                break;
+            }
+
+            self.readers.fetch_sub(1, Ordering::Release);
+        }
+
+        struct Guard<'a>(&'a AtomicUsize);
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::Release);
+            }
+        }
+
+        let _guard = Guard(&self.readers);
+
+        f()
+    }
+
+    /// Runs `f` once all draws and advances have finished, keeping new ones from starting.
+    pub fn write<T>(&self, f: impl FnOnce() -> T) -> T {
+        while self
+            .writer
+            .compare_exchange_weak(false, true, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+
+        struct Guard<'a>(&'a AtomicBool);
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::Release);
+            }
+        }
+
+        let _guard = Guard(&self.writer);
+
+        while self.readers.load(Ordering::SeqCst) != 0 {
+            hint::spin_loop();
+        }
+
+        f()
+    }
+}
+
+pub(crate) struct FileInner {
+    pub raw_file: *const ffi::File,
+    raw_factory: *mut ffi::Factory,
+    pub assets_lock: AssetLock,
+    // Boxed twice so that the C++ side can hold on to a thin pointer.
+    _asset_loader: Option<Box<Box<dyn AssetLoader>>>,
+}
+
+impl fmt::Debug for FileInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileInner")
+            .field("raw_file", &self.raw_file)
+            .field("raw_factory", &self.raw_factory)
+            .finish()
+    }
+}
+
+impl Drop for FileInner {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rive_rs_file_release(self.raw_file, self.raw_factory);
+        }
+    }
+}
+
+unsafe impl Send for FileInner {}
+unsafe impl Sync for FileInner {}
+
+pub struct FileBuilder<R: Renderer> {
+    asset_loader: Option<Box<Box<dyn AssetLoader>>>,
+    _phantom: PhantomData<R>,
+}
+
+impl<R: Renderer> FileBuilder<R> {
+    /// Sets the loader that gets called for every asset referenced by the file while importing.
+    #[inline]
+    pub fn asset_loader(mut self, asset_loader: impl AssetLoader + 'static) -> Self {
+        self.asset_loader = Some(Box::new(Box::new(asset_loader)));
+        self
+    }
+
+    pub fn build(self, data: &[u8]) -> Result<File<R>, Error> {
+        let mut result = ffi::FileResult::default();
+        let mut raw_factory = ptr::null_mut();
+        let mut asset_loader = self.asset_loader;
+
+        let raw_asset_loader = asset_loader
+            .as_deref_mut()
+            .map(|asset_loader| asset_loader as *mut Box<dyn AssetLoader> as *mut ())
+            .unwrap_or(ptr::null_mut());
+
+        let raw_file = unsafe {
+            ffi::rive_rs_file_new(
+                data.as_ptr(),
+                data.len(),
+                ffi::RendererEntries::<R>::ENTRIES as *const ffi::RendererEntries<R> as *const (),
+                raw_asset_loader,
+                &mut result as *mut ffi::FileResult,
+                &mut raw_factory as *mut *mut ffi::Factory,
+            )
+        };
+
+        match result.kind {
+            ffi::FileResultKind::Success => Ok(File {
+                inner: Arc::new(FileInner {
+                    raw_file,
+                    raw_factory,
+                    assets_lock: AssetLock::default(),
+                    _asset_loader: asset_loader,
+                }),
+                _phantom: PhantomData,
+            }),
+            ffi::FileResultKind::UnsupportedVersion => Err(Error::UnsupportedVersion {
+                file: Version {
+                    major: result.major_version,
+                    minor: result.minor_version,
+                },
+                supported: Version {
+                    major: result.supported_major_version,
+                    minor: result.supported_minor_version,
+                },
+            }),
+            ffi::FileResultKind::Malformed => Err(Error::Malformed {
+                offset: result.has_offset.then_some(result.offset),
+                type_key: result.has_type_key.then_some(result.type_key),
+            }),
+        }
+    }
+}
+
+impl<R: Renderer> Default for FileBuilder<R> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            asset_loader: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R: Renderer> fmt::Debug for FileBuilder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileBuilder")
+            .field("asset_loader", &self.asset_loader.is_some())
+            .finish()
+    }
+}
+
+pub struct File<R: Renderer> {
+    inner: Arc<FileInner>,
+    _phantom: PhantomData<R>,
+}
+
+impl<R: Renderer> File<R> {
+    #[inline]
+    pub fn new(data: &[u8]) -> Result<Self, Error> {
+        Self::builder().build(data)
+    }
+
+    #[inline]
+    pub fn builder() -> FileBuilder<R> {
+        FileBuilder::default()
+    }
+
+    /// Iterates over descriptions of all the artboards of the file, together with their
+    /// animations and state machines, without instantiating any of them.
+    #[inline]
+    pub fn artboards(&self) -> ArtboardDescriptors {
+        ArtboardDescriptors::new(descriptors::RawFileArtboards(self.inner.raw_file))
+    }
+
+    /// Iterates over all the assets of the file. Swapping an asset's contents with
+    /// [`ImageAsset::set_image`](assets::ImageAsset::set_image) or
+    /// [`FontAsset::set_font`](assets::FontAsset::set_font) affects every artboard instantiated
+    /// from this file.
+    #[inline]
+    pub fn assets(&mut self) -> Assets {
+        Assets::new(assets::RawFile {
+            raw_file: self.inner.raw_file,
+            raw_factory: self.inner.raw_factory,
+            assets_lock: &self.inner.assets_lock as *const AssetLock,
+        })
+    }
+
+    /// Decodes `bytes` into the asset with identifier `id`, e.g. one which an
+    /// [`AssetLoader`] chose to load later. Artboards that were already instantiated from the file
+    /// draw the asset from their next frame on. Returns `false` if there is no such asset or if
+    /// the bytes could not be decoded.
+    pub fn load_asset(&mut self, id: u32, bytes: &[u8]) -> bool {
+        self.inner.assets_lock.write(|| unsafe {
+            ffi::rive_rs_file_load_asset(
+                self.inner.raw_file,
+                id,
+                bytes.as_ptr(),
+                bytes.len(),
+                self.inner.raw_factory,
+            )
+        })
+    }
+
+    pub(crate) fn as_inner(&self) -> &Arc<FileInner> {
+        &self.inner
+    }
+}
+
+impl<R: Renderer> fmt::Debug for File<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("File").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{thread, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn swaps_wait_for_draws() {
+        let lock = Arc::new(AssetLock::default());
+
+        let swap = lock.read(|| {
+            let lock = lock.clone();
+            let swap = thread::spawn(move || lock.write(|| ()));
+
+            thread::sleep(Duration::from_millis(50));
+            assert!(!swap.is_finished());
+
+            swap
+        });
+
+        swap.join().unwrap();
+        assert_eq!(lock.read(|| 1), 1);
+    }
+}
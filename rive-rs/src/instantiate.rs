@@ -1,6 +1,7 @@
 use alloc::borrow::Cow;
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Handle {
     #[default]
     Default,
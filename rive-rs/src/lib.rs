@@ -1,4 +1,12 @@
-#![cfg_attr(not(feature = "vello"), no_std)]
+#![cfg_attr(
+    not(any(
+        feature = "software",
+        feature = "svg",
+        feature = "tessellation",
+        feature = "vello"
+    )),
+    no_std
+)]
 
 //! # Rive runtime bindings
 //!
@@ -42,37 +50,58 @@ for r in 1..=5 {
 extern crate alloc;
 
 mod artboard;
+#[cfg(feature = "export")]
+pub mod export;
 mod ffi;
 mod file;
 mod instantiate;
 mod linear_animation;
+mod mixer;
 pub mod path;
 mod raw_iter;
+#[cfg(feature = "recording")]
+pub mod recording;
 pub mod renderer;
 pub mod scene;
+#[cfg(feature = "software")]
+pub mod software;
 pub mod state_machine;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "tessellation")]
+pub mod tessellation;
+#[cfg(any(feature = "software", feature = "svg", feature = "vello"))]
+mod util;
 #[cfg(feature = "vello")]
 pub mod vello;
 
 pub use crate::{
-    artboard::components,
-    file::Error,
+    artboard::{components, Bounds},
+    file::{assets, descriptors, Error, Version},
     instantiate::{Handle, Instantiate},
     linear_animation::{Direction, Loop},
-    scene::Viewport,
+    scene::{Alignment, Fit, HitResult, Viewport},
 };
 
 #[cfg(not(feature = "vello"))]
 pub use crate::{
-    artboard::Artboard, file::File, linear_animation::LinearAnimation, scene::Scene,
+    artboard::Artboard,
+    file::{File, FileBuilder},
+    linear_animation::LinearAnimation,
+    mixer::AnimationMixer,
+    scene::Scene,
     state_machine::StateMachine,
 };
 
+#[cfg(feature = "vello")]
+pub type AnimationMixer = mixer::AnimationMixer<crate::vello::Renderer>;
 #[cfg(feature = "vello")]
 pub type Artboard = artboard::Artboard<crate::vello::Renderer>;
 #[cfg(feature = "vello")]
 pub type File = file::File<crate::vello::Renderer>;
 #[cfg(feature = "vello")]
+pub type FileBuilder = file::FileBuilder<crate::vello::Renderer>;
+#[cfg(feature = "vello")]
 pub type LinearAnimation = linear_animation::LinearAnimation<crate::vello::Renderer>;
 #[cfg(feature = "vello")]
 pub type StateMachine = state_machine::StateMachine<crate::vello::Renderer>;
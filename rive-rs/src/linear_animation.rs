@@ -10,6 +10,7 @@ use crate::{
 };
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Direction {
     Forwards,
     Backwards,
@@ -17,6 +18,7 @@ pub enum Direction {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Loop {
     /// Play until the duration or end of work area of the animation.
     OneShot = 0,
@@ -74,7 +76,7 @@ impl<R: Renderer> Instantiate for LinearAnimation<R> {
 }
 
 impl<R: Renderer> LinearAnimation<R> {
-    fn raw_artboard(&self) -> *mut ffi::Artboard {
+    pub(crate) fn raw_artboard(&self) -> *mut ffi::Artboard {
         self.artboard.raw_artboard
     }
 
@@ -86,6 +88,11 @@ impl<R: Renderer> LinearAnimation<R> {
         self.raw_linear_animation as *mut ffi::Scene
     }
 
+    /// Linear animations don't have listeners.
+    fn raw_hit_test(&self, _x: f32, _y: f32) -> bool {
+        false
+    }
+
     pub fn time(&self) -> Duration {
         Duration::from_secs_f32(unsafe {
             ffi::rive_rs_linear_animation_time(self.raw_linear_animation)
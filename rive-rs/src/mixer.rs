@@ -0,0 +1,188 @@
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
+
+use crate::{
+    artboard::Artboard,
+    instantiate::{Handle, Instantiate},
+    linear_animation::LinearAnimation,
+    renderer::Renderer,
+};
+
+#[derive(Clone, Copy, Debug)]
+struct Fade {
+    from: f32,
+    to: f32,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl Fade {
+    fn advance(&mut self, elapsed: Duration) -> f32 {
+        self.elapsed = (self.elapsed + elapsed).min(self.duration);
+
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+
+        self.from + (self.to - self.from) * t
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+struct Layer<R: Renderer> {
+    animation: LinearAnimation<R>,
+    weight: f32,
+    fade: Option<Fade>,
+}
+
+/// Blends several [`LinearAnimation`]s of the same [`Artboard`] together, e.g. to transition
+/// between the idle and walk cycles of a character.
+///
+/// Every frame, each layer is advanced independently and applied in order with its weight, after
+/// which the artboard is advanced once. Layers are identified by their index, in the order in
+/// which they were added.
+pub struct AnimationMixer<R: Renderer> {
+    artboard: Artboard<R>,
+    layers: Vec<Layer<R>>,
+}
+
+impl<R: Renderer> AnimationMixer<R> {
+    pub fn new(artboard: &Artboard<R>) -> Self {
+        Self {
+            artboard: Artboard::from_inner(artboard.as_inner().clone()),
+            layers: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn artboard(&self) -> &Artboard<R> {
+        &self.artboard
+    }
+
+    /// Instantiates the animation identified by `handle` on top of the existing layers and
+    /// returns the index of its layer, or `None` if the artboard has no such animation.
+    pub fn add(&mut self, handle: Handle, weight: f32) -> Option<usize> {
+        let animation = LinearAnimation::instantiate(&self.artboard, handle)?;
+
+        self.layers.push(Layer {
+            animation,
+            weight,
+            fade: None,
+        });
+
+        Some(self.layers.len() - 1)
+    }
+
+    /// Removes the layer at `index` and returns its animation. The layers above it move down
+    /// by one index.
+    pub fn remove(&mut self, index: usize) -> Option<LinearAnimation<R>> {
+        (index < self.layers.len()).then(|| self.layers.remove(index).animation)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    #[inline]
+    pub fn animation(&self, index: usize) -> Option<&LinearAnimation<R>> {
+        self.layers.get(index).map(|layer| &layer.animation)
+    }
+
+    #[inline]
+    pub fn animation_mut(&mut self, index: usize) -> Option<&mut LinearAnimation<R>> {
+        self.layers.get_mut(index).map(|layer| &mut layer.animation)
+    }
+
+    #[inline]
+    pub fn weight(&self, index: usize) -> Option<f32> {
+        self.layers.get(index).map(|layer| layer.weight)
+    }
+
+    /// Sets the weight of a layer, canceling any fade in progress on it.
+    #[inline]
+    pub fn set_weight(&mut self, index: usize, weight: f32) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.weight = weight;
+            layer.fade = None;
+        }
+    }
+
+    /// Linearly changes the weight of a layer to `weight` over `duration`.
+    pub fn fade_to(&mut self, index: usize, weight: f32, duration: Duration) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.fade = Some(Fade {
+                from: layer.weight,
+                to: weight,
+                elapsed: Duration::ZERO,
+                duration,
+            });
+        }
+    }
+
+    /// Fades layer `from` out and layer `to` in over `duration`.
+    #[inline]
+    pub fn cross_fade(&mut self, from: usize, to: usize, duration: Duration) {
+        self.fade_to(from, 0.0, duration);
+        self.fade_to(to, 1.0, duration);
+    }
+
+    /// Returns `true` while a weight is still fading.
+    #[inline]
+    pub fn is_fading(&self) -> bool {
+        self.layers.iter().any(|layer| layer.fade.is_some())
+    }
+
+    /// Advances all layers and their fades by `elapsed`, applies them in order and then advances
+    /// the artboard. Layers with a weight of zero are advanced but not applied. Returns `true`
+    /// while any layer keeps playing or fading.
+    pub fn advance_and_apply(&mut self, elapsed: Duration) -> bool {
+        let mut keep_going = false;
+
+        for layer in &mut self.layers {
+            if let Some(fade) = &mut layer.fade {
+                layer.weight = fade.advance(elapsed);
+
+                if fade.is_done() {
+                    layer.fade = None;
+                } else {
+                    keep_going = true;
+                }
+            }
+
+            keep_going |= layer.animation.advance(elapsed);
+
+            if layer.weight > 0.0 {
+                layer.animation.apply(layer.weight);
+            }
+        }
+
+        self.artboard.advance(elapsed);
+
+        keep_going
+    }
+
+    /// Draws the artboard in artboard space with `renderer`'s current transform.
+    #[inline]
+    pub fn draw(&self, renderer: &mut R) {
+        self.artboard.draw(renderer);
+    }
+}
+
+impl<R: Renderer> fmt::Debug for AnimationMixer<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimationMixer")
+            .field("len", &self.layers.len())
+            .finish()
+    }
+}
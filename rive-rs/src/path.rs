@@ -11,6 +11,7 @@ pub struct Point {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum FillRule {
     NonZero,
     EvenOdd,
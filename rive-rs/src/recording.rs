@@ -0,0 +1,684 @@
+//! A [`Renderer`](renderer::Renderer) back-end that records draw calls into an owned
+//! [`DisplayList`] which can later be replayed onto any other renderer, e.g. to cache the frames
+//! of static scenes, to snapshot draw calls in tests or to send frames to another thread.
+
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::mem;
+
+use crate::{
+    path::FillRule,
+    renderer::{
+        self, BlendMode, BufferFlags, BufferType, Color, ImageSampler, PaintStyle, StrokeCap,
+        StrokeJoin,
+    },
+};
+
+const TRANSPARENT: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0,
+};
+
+fn to_bytes<const N: usize>(values: impl Iterator<Item = [u8; N]>) -> Vec<u8> {
+    values.flatten().collect()
+}
+
+fn new_buffer<B: renderer::Buffer>(r#type: BufferType, bytes: &[u8]) -> B {
+    let mut buffer = B::new(r#type, BufferFlags::NONE, bytes.len());
+    buffer.map().copy_from_slice(bytes);
+    buffer.unmap();
+
+    buffer
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    inner: Vec<u8>,
+}
+
+impl Buffer {
+    pub fn to_f32_pairs(&self) -> Vec<[f32; 2]> {
+        self.inner
+            .chunks_exact(8)
+            .map(|pair| {
+                [
+                    f32::from_ne_bytes([pair[0], pair[1], pair[2], pair[3]]),
+                    f32::from_ne_bytes([pair[4], pair[5], pair[6], pair[7]]),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn to_u16s(&self) -> Vec<u16> {
+        self.inner
+            .chunks_exact(2)
+            .map(|bytes| u16::from_ne_bytes([bytes[0], bytes[1]]))
+            .collect()
+    }
+}
+
+impl renderer::Buffer for Buffer {
+    #[inline]
+    fn new(_type: BufferType, _flags: BufferFlags, len_in_bytes: usize) -> Self {
+        Self {
+            inner: vec![0; len_in_bytes],
+        }
+    }
+
+    #[inline]
+    fn map(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn unmap(&mut self) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PathCommand {
+    MoveTo {
+        x: f32,
+        y: f32,
+    },
+    LineTo {
+        x: f32,
+        y: f32,
+    },
+    CubicTo {
+        ox: f32,
+        oy: f32,
+        ix: f32,
+        iy: f32,
+        x: f32,
+        y: f32,
+    },
+    Close,
+}
+
+impl PathCommand {
+    fn transform(self, transform: &[f32; 6]) -> Self {
+        let apply = |x: f32, y: f32| {
+            (
+                transform[0] * x + transform[2] * y + transform[4],
+                transform[1] * x + transform[3] * y + transform[5],
+            )
+        };
+
+        match self {
+            Self::MoveTo { x, y } => {
+                let (x, y) = apply(x, y);
+                Self::MoveTo { x, y }
+            }
+            Self::LineTo { x, y } => {
+                let (x, y) = apply(x, y);
+                Self::LineTo { x, y }
+            }
+            Self::CubicTo {
+                ox,
+                oy,
+                ix,
+                iy,
+                x,
+                y,
+            } => {
+                let (ox, oy) = apply(ox, oy);
+                let (ix, iy) = apply(ix, iy);
+                let (x, y) = apply(x, y);
+                Self::CubicTo {
+                    ox,
+                    oy,
+                    ix,
+                    iy,
+                    x,
+                    y,
+                }
+            }
+            Self::Close => Self::Close,
+        }
+    }
+}
+
+/// A path, recorded with its contents at the time it was drawn.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    pub fill_rule: FillRule,
+}
+
+impl Path {
+    fn replay<R: renderer::Renderer>(&self) -> R::Path {
+        use renderer::Path as _;
+
+        let mut path = R::Path::default();
+
+        path.set_fill_rule(self.fill_rule);
+
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo { x, y } => path.move_to(x, y),
+                PathCommand::LineTo { x, y } => path.line_to(x, y),
+                PathCommand::CubicTo {
+                    ox,
+                    oy,
+                    ix,
+                    iy,
+                    x,
+                    y,
+                } => path.cubic_to(ox, oy, ix, iy, x, y),
+                PathCommand::Close => path.close(),
+            }
+        }
+
+        path
+    }
+}
+
+impl Default for Path {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            fill_rule: FillRule::NonZero,
+        }
+    }
+}
+
+impl renderer::Path for Path {
+    fn new(commands: &mut crate::path::Commands, fill_rule: FillRule) -> Self {
+        let mut path = Self::default();
+
+        for (verb, points) in commands {
+            match verb {
+                crate::path::Verb::Move => path.move_to(points[0].x, points[0].y),
+                crate::path::Verb::Line => path.line_to(points[0].x, points[0].y),
+                crate::path::Verb::Cubic => path.cubic_to(
+                    points[0].x,
+                    points[0].y,
+                    points[1].x,
+                    points[1].y,
+                    points[2].x,
+                    points[2].y,
+                ),
+                crate::path::Verb::Close => path.close(),
+            }
+        }
+
+        path.set_fill_rule(fill_rule);
+
+        path
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.commands.clear();
+    }
+
+    #[inline]
+    fn extend(&mut self, from: &Self, transform: &[f32; 6]) {
+        self.commands.extend(
+            from.commands
+                .iter()
+                .map(|command| command.transform(transform)),
+        );
+    }
+
+    #[inline]
+    fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.commands.push(PathCommand::MoveTo { x, y });
+    }
+
+    #[inline]
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.commands.push(PathCommand::LineTo { x, y });
+    }
+
+    #[inline]
+    fn cubic_to(&mut self, ox: f32, oy: f32, ix: f32, iy: f32, x: f32, y: f32) {
+        self.commands.push(PathCommand::CubicTo {
+            ox,
+            oy,
+            ix,
+            iy,
+            x,
+            y,
+        });
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.commands.push(PathCommand::Close);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Gradient {
+    Linear {
+        start: [f32; 2],
+        end: [f32; 2],
+        stops: Vec<GradientStop>,
+    },
+    Radial {
+        center: [f32; 2],
+        radius: f32,
+        stops: Vec<GradientStop>,
+    },
+}
+
+impl Gradient {
+    fn replay<R: renderer::Renderer>(&self) -> R::Gradient {
+        use renderer::Gradient as _;
+
+        let stops = match self {
+            Self::Linear { stops, .. } | Self::Radial { stops, .. } => stops,
+        };
+        let colors: Vec<_> = stops.iter().map(|stop| stop.color).collect();
+        let offsets: Vec<_> = stops.iter().map(|stop| stop.offset).collect();
+
+        match *self {
+            Self::Linear {
+                start: [sx, sy],
+                end: [ex, ey],
+                ..
+            } => R::Gradient::new_linear(sx, sy, ex, ey, &colors, &offsets),
+            Self::Radial {
+                center: [cx, cy],
+                radius,
+                ..
+            } => R::Gradient::new_radial(cx, cy, radius, &colors, &offsets),
+        }
+    }
+}
+
+fn gradient_stops(colors: &[Color], stops: &[f32]) -> Vec<GradientStop> {
+    colors
+        .iter()
+        .zip(stops.iter())
+        .map(|(&color, &offset)| GradientStop { offset, color })
+        .collect()
+}
+
+impl renderer::Gradient for Gradient {
+    #[inline]
+    fn new_linear(sx: f32, sy: f32, ex: f32, ey: f32, colors: &[Color], stops: &[f32]) -> Self {
+        Self::Linear {
+            start: [sx, sy],
+            end: [ex, ey],
+            stops: gradient_stops(colors, stops),
+        }
+    }
+
+    #[inline]
+    fn new_radial(cx: f32, cy: f32, radius: f32, colors: &[Color], stops: &[f32]) -> Self {
+        Self::Radial {
+            center: [cx, cy],
+            radius,
+            stops: gradient_stops(colors, stops),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Brush {
+    Color(Color),
+    Gradient(Gradient),
+}
+
+/// A paint, recorded with its properties at the time it was used.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Paint {
+    pub style: PaintStyle,
+    pub brush: Brush,
+    pub thickness: f32,
+    pub join: StrokeJoin,
+    pub cap: StrokeCap,
+    pub blend_mode: BlendMode,
+}
+
+impl Paint {
+    fn replay<R: renderer::Renderer>(&self) -> R::Paint {
+        use renderer::Paint as _;
+
+        let mut paint = R::Paint::default();
+
+        paint.set_style(self.style);
+        match &self.brush {
+            Brush::Color(color) => paint.set_color(*color),
+            Brush::Gradient(gradient) => paint.set_gradient(&gradient.replay::<R>()),
+        }
+        if self.style == PaintStyle::Stroke {
+            paint.set_thickness(self.thickness);
+            paint.set_join(self.join);
+            paint.set_cap(self.cap);
+        }
+        paint.set_blend_mode(self.blend_mode);
+
+        paint
+    }
+}
+
+impl Default for Paint {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            style: PaintStyle::Fill,
+            brush: Brush::Color(TRANSPARENT),
+            thickness: 1.0,
+            join: StrokeJoin::Miter,
+            cap: StrokeCap::Butt,
+            blend_mode: BlendMode::SrcOver,
+        }
+    }
+}
+
+impl renderer::Paint for Paint {
+    type Gradient = Gradient;
+
+    #[inline]
+    fn set_style(&mut self, style: PaintStyle) {
+        self.style = style;
+    }
+
+    #[inline]
+    fn set_color(&mut self, color: Color) {
+        self.brush = Brush::Color(color);
+    }
+
+    #[inline]
+    fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness;
+    }
+
+    #[inline]
+    fn set_join(&mut self, join: StrokeJoin) {
+        self.join = join;
+    }
+
+    #[inline]
+    fn set_cap(&mut self, cap: StrokeCap) {
+        self.cap = cap;
+    }
+
+    #[inline]
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    #[inline]
+    fn set_gradient(&mut self, gradient: &Self::Gradient) {
+        self.brush = Brush::Gradient(gradient.clone());
+    }
+
+    #[inline]
+    fn invalidate_stroke(&mut self) {}
+}
+
+/// An image, kept encoded so that it can be decoded again by the renderer it is replayed onto.
+#[derive(Debug)]
+pub struct Image {
+    bytes: Arc<[u8]>,
+}
+
+impl renderer::Image for Image {
+    #[inline]
+    fn decode(data: &[u8]) -> Option<Self> {
+        Some(Self { bytes: data.into() })
+    }
+}
+
+/// A recorded call of a [`Renderer`](renderer::Renderer) method. Images are referenced by their
+/// index in [`DisplayList::images`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Command {
+    StatePush,
+    StatePop,
+    Transform([f32; 6]),
+    SetClip(Path),
+    DrawPath {
+        path: Path,
+        paint: Paint,
+    },
+    DrawImage {
+        image: usize,
+        sampler: ImageSampler,
+        blend_mode: BlendMode,
+        opacity: f32,
+    },
+    DrawImageMesh {
+        image: usize,
+        sampler: ImageSampler,
+        vertices: Vec<[f32; 2]>,
+        uvs: Vec<[f32; 2]>,
+        indices: Vec<u16>,
+        blend_mode: BlendMode,
+        opacity: f32,
+    },
+}
+
+/// Owned list of the draw calls of a frame, recorded by a [`Renderer`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DisplayList {
+    commands: Vec<Command>,
+    images: Vec<Arc<[u8]>>,
+}
+
+impl DisplayList {
+    #[inline]
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Encoded bytes of the images drawn by the list, each stored once.
+    #[inline]
+    pub fn images(&self) -> &[Arc<[u8]>] {
+        &self.images
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Replays all recorded calls onto `renderer`. Every image is decoded once per replay; draw
+    /// calls of images that `renderer` cannot decode are skipped.
+    pub fn replay<R: renderer::Renderer>(&self, renderer: &mut R) {
+        use renderer::Image as _;
+
+        let images: Vec<_> = self
+            .images
+            .iter()
+            .map(|bytes| R::Image::decode(bytes))
+            .collect();
+
+        for command in &self.commands {
+            match command {
+                Command::StatePush => renderer.state_push(),
+                Command::StatePop => renderer.state_pop(),
+                Command::Transform(transform) => renderer.transform(transform),
+                Command::SetClip(path) => renderer.set_clip(&path.replay::<R>()),
+                Command::DrawPath { path, paint } => {
+                    renderer.draw_path(&path.replay::<R>(), &paint.replay::<R>())
+                }
+                Command::DrawImage {
+                    image,
+                    sampler,
+                    blend_mode,
+                    opacity,
+                } => {
+                    if let Some(Some(image)) = images.get(*image) {
+                        renderer.draw_image(image, *sampler, *blend_mode, *opacity);
+                    }
+                }
+                Command::DrawImageMesh {
+                    image,
+                    sampler,
+                    vertices,
+                    uvs,
+                    indices,
+                    blend_mode,
+                    opacity,
+                } => {
+                    if let Some(Some(image)) = images.get(*image) {
+                        let vertices = to_bytes(
+                            vertices
+                                .iter()
+                                .flat_map(|vertex| vertex.map(f32::to_ne_bytes)),
+                        );
+                        let uvs = to_bytes(uvs.iter().flat_map(|uv| uv.map(f32::to_ne_bytes)));
+                        let indices = to_bytes(indices.iter().map(|index| index.to_ne_bytes()));
+
+                        renderer.draw_image_mesh(
+                            image,
+                            *sampler,
+                            &new_buffer(BufferType::Vertex, &vertices),
+                            &new_buffer(BufferType::Vertex, &uvs),
+                            &new_buffer(BufferType::Index, &indices),
+                            *blend_mode,
+                            *opacity,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Records draw calls into a [`DisplayList`].
+#[derive(Debug, Default)]
+pub struct Renderer {
+    display_list: DisplayList,
+}
+
+impl Renderer {
+    #[inline]
+    pub fn display_list(&self) -> &DisplayList {
+        &self.display_list
+    }
+
+    /// Returns the calls recorded so far and starts recording a new list, e.g. for the next frame.
+    #[inline]
+    pub fn take_display_list(&mut self) -> DisplayList {
+        mem::take(&mut self.display_list)
+    }
+
+    #[inline]
+    pub fn into_display_list(self) -> DisplayList {
+        self.display_list
+    }
+
+    fn image_index(&mut self, image: &Image) -> usize {
+        let images = &mut self.display_list.images;
+
+        images
+            .iter()
+            .position(|bytes| Arc::ptr_eq(bytes, &image.bytes))
+            .unwrap_or_else(|| {
+                images.push(image.bytes.clone());
+                images.len() - 1
+            })
+    }
+}
+
+impl renderer::Renderer for Renderer {
+    type Buffer = Buffer;
+
+    type Path = Path;
+
+    type Paint = Paint;
+
+    type Gradient = Gradient;
+
+    type Image = Image;
+
+    #[inline]
+    fn state_push(&mut self) {
+        self.display_list.commands.push(Command::StatePush);
+    }
+
+    #[inline]
+    fn state_pop(&mut self) {
+        self.display_list.commands.push(Command::StatePop);
+    }
+
+    #[inline]
+    fn transform(&mut self, transform: &[f32; 6]) {
+        self.display_list
+            .commands
+            .push(Command::Transform(*transform));
+    }
+
+    #[inline]
+    fn set_clip(&mut self, path: &Self::Path) {
+        self.display_list
+            .commands
+            .push(Command::SetClip(path.clone()));
+    }
+
+    #[inline]
+    fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint) {
+        self.display_list.commands.push(Command::DrawPath {
+            path: path.clone(),
+            paint: paint.clone(),
+        });
+    }
+
+    #[inline]
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: ImageSampler,
+        blend_mode: BlendMode,
+        opacity: f32,
+    ) {
+        let image = self.image_index(image);
+
+        self.display_list.commands.push(Command::DrawImage {
+            image,
+            sampler,
+            blend_mode,
+            opacity,
+        });
+    }
+
+    fn draw_image_mesh(
+        &mut self,
+        image: &Self::Image,
+        sampler: ImageSampler,
+        vertices: &Self::Buffer,
+        uvs: &Self::Buffer,
+        indices: &Self::Buffer,
+        blend_mode: BlendMode,
+        opacity: f32,
+    ) {
+        let image = self.image_index(image);
+
+        self.display_list.commands.push(Command::DrawImageMesh {
+            image,
+            sampler,
+            vertices: vertices.to_f32_pairs(),
+            uvs: uvs.to_f32_pairs(),
+            indices: indices.to_u16s(),
+            blend_mode,
+            opacity,
+        });
+    }
+}
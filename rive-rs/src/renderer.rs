@@ -20,6 +20,7 @@ bitflags! {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StrokeJoin {
     Miter = 0,
     Round = 1,
@@ -28,6 +29,7 @@ pub enum StrokeJoin {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StrokeCap {
     Butt = 0,
     Round = 1,
@@ -36,6 +38,7 @@ pub enum StrokeCap {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum BlendMode {
     SrcOver = 3,
     Screen = 14,
@@ -57,13 +60,50 @@ pub enum BlendMode {
 
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PaintStyle {
     Stroke,
     Fill,
 }
 
+/// How an image is sampled outside of the `0.0..=1.0` UV range.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageWrap {
+    #[default]
+    Clamp,
+    Repeat,
+    Mirror,
+}
+
+/// How an image is sampled between its pixels.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageFilter {
+    #[default]
+    Bilinear,
+    /// Keeps pixels sharp, e.g. for pixel art.
+    Nearest,
+    /// Bilinear filtering between mipmap levels. Back-ends without mipmaps fall back to
+    /// [`ImageFilter::Bilinear`].
+    Mipmap,
+}
+
+/// Sampling options of an image.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ImageSampler {
+    pub wrap_x: ImageWrap,
+    pub wrap_y: ImageWrap,
+    pub filter: ImageFilter,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Color {
     pub b: u8,
     pub g: u8,
@@ -122,10 +162,18 @@ pub trait Renderer: Sized + 'static {
     fn transform(&mut self, transform: &[f32; 6]);
     fn set_clip(&mut self, path: &Self::Path);
     fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint);
-    fn draw_image(&mut self, image: &Self::Image, blend_mode: BlendMode, opacity: f32);
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: ImageSampler,
+        blend_mode: BlendMode,
+        opacity: f32,
+    );
+    #[allow(clippy::too_many_arguments)]
     fn draw_image_mesh(
         &mut self,
         image: &Self::Image,
+        sampler: ImageSampler,
         vertices: &Self::Buffer,
         uvs: &Self::Buffer,
         indices: &Self::Buffer,
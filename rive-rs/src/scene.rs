@@ -4,6 +4,7 @@ use alloc::boxed::Box;
 
 use crate::{
     artboard::Artboard,
+    ffi,
     instantiate::{Handle, Instantiate},
     linear_animation::{LinearAnimation, Loop},
     renderer::Renderer,
@@ -14,11 +15,73 @@ pub(crate) fn transform(x: f32, y: f32, t: &[f32; 6]) -> [f32; 2] {
     [t[0] * x + t[2] * y + t[4], t[1] * x + t[3] * y + t[5]]
 }
 
-#[derive(Clone, Debug)]
+/// How an artboard is scaled to fit inside of a [`Viewport`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Fit {
+    /// Stretch the artboard to fill the viewport, ignoring its aspect ratio.
+    Fill,
+    /// Scale the artboard uniformly so that it fits entirely inside the viewport.
+    #[default]
+    Contain,
+    /// Scale the artboard uniformly so that it covers the whole viewport.
+    Cover,
+    /// Scale the artboard uniformly so that its width matches the viewport's.
+    FitWidth,
+    /// Scale the artboard uniformly so that its height matches the viewport's.
+    FitHeight,
+    /// Keep the artboard at its original size.
+    None,
+    /// Like [`Fit::Contain`], but never scale the artboard up.
+    ScaleDown,
+    /// Resize the artboard to match the viewport and let its layout fill the space. The artboard
+    /// is resized by [`Scene::advance_and_maybe_draw`].
+    Layout,
+}
+
+/// Where an artboard is positioned inside of a [`Viewport`].
+///
+/// Both coordinates range from `-1.0` (left/top) to `1.0` (right/bottom).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Alignment {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Alignment {
+    pub const TOP_LEFT: Self = Self::new(-1.0, -1.0);
+    pub const TOP_CENTER: Self = Self::new(0.0, -1.0);
+    pub const TOP_RIGHT: Self = Self::new(1.0, -1.0);
+    pub const CENTER_LEFT: Self = Self::new(-1.0, 0.0);
+    pub const CENTER: Self = Self::new(0.0, 0.0);
+    pub const CENTER_RIGHT: Self = Self::new(1.0, 0.0);
+    pub const BOTTOM_LEFT: Self = Self::new(-1.0, 1.0);
+    pub const BOTTOM_CENTER: Self = Self::new(0.0, 1.0);
+    pub const BOTTOM_RIGHT: Self = Self::new(1.0, 1.0);
+
+    #[inline]
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Default for Alignment {
+    #[inline]
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Viewport {
     pub(crate) width: u32,
     pub(crate) height: u32,
-    pub(crate) inverse_view_transform: [f32; 6],
+    pub(crate) fit: Fit,
+    pub(crate) alignment: Alignment,
 }
 
 impl Viewport {
@@ -37,19 +100,86 @@ impl Viewport {
         self.width = width;
         self.height = height;
     }
+
+    #[inline]
+    pub fn fit(&self) -> Fit {
+        self.fit
+    }
+
+    #[inline]
+    pub fn set_fit(&mut self, fit: Fit) {
+        self.fit = fit;
+    }
+
+    #[inline]
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    #[inline]
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
 }
 
-impl Default for Viewport {
+/// Whether a pointer event hit a listener of the scene.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum HitResult {
+    /// No listener was hit.
+    #[default]
+    None,
+    /// A listener was hit, but elements underneath it can still be hit.
+    Hit,
+    /// A listener marked as opaque was hit, i.e. the event should not be propagated to the
+    /// elements underneath the scene.
+    HitOpaque,
+}
+
+impl HitResult {
     #[inline]
-    fn default() -> Self {
-        Self {
-            width: 0,
-            height: 0,
-            inverse_view_transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    pub fn is_hit(self) -> bool {
+        self != Self::None
+    }
+}
+
+/// Resizes the artboard to match the `viewport` when it uses [`Fit::Layout`]. This needs to
+/// happen before the artboard is advanced so that its layout is computed for the new size.
+pub(crate) fn resize_to_viewport(raw_artboard: *mut ffi::Artboard, viewport: &Viewport) {
+    if viewport.fit == Fit::Layout {
+        unsafe {
+            ffi::rive_rs_artboard_set_width(raw_artboard, viewport.width as f32);
+            ffi::rive_rs_artboard_set_height(raw_artboard, viewport.height as f32);
         }
     }
 }
 
+/// Computes the transform that maps the artboard into the `viewport` together with its inverse,
+/// which maps viewport coordinates back into the artboard.
+pub(crate) fn view_transforms(
+    raw_artboard: *mut ffi::Artboard,
+    viewport: &Viewport,
+) -> ([f32; 6], [f32; 6]) {
+    let mut view_transform = [0.0; 6];
+    let mut inverse_view_transform = [0.0; 6];
+
+    unsafe {
+        ffi::rive_rs_artboard_instance_transforms(
+            raw_artboard,
+            viewport.width,
+            viewport.height,
+            viewport.fit,
+            viewport.alignment.x,
+            viewport.alignment.y,
+            view_transform.as_mut_ptr(),
+            inverse_view_transform.as_mut_ptr(),
+        );
+    }
+
+    (view_transform, inverse_view_transform)
+}
+
 pub trait Scene<R: Renderer>: Send + Sync {
     fn width(&self) -> f32;
     fn height(&self) -> f32;
@@ -57,9 +187,61 @@ pub trait Scene<R: Renderer>: Send + Sync {
     fn r#loop(&self) -> Loop;
     fn is_translucent(&self) -> bool;
     fn duration(&self) -> Option<Duration>;
-    fn pointer_down(&mut self, x: f32, y: f32, viewport: &Viewport);
-    fn pointer_move(&mut self, x: f32, y: f32, viewport: &Viewport);
-    fn pointer_up(&mut self, x: f32, y: f32, viewport: &Viewport);
+    /// Returns whether a pointer at viewport coordinates `x`, `y` would hit a listener of the
+    /// scene, without dispatching any event to it.
+    fn hit_test(&self, x: f32, y: f32, viewport: &Viewport) -> bool;
+    /// Same as [`Scene::pointer_down_with_id`] with a pointer id of `0`.
+    #[inline]
+    fn pointer_down(&mut self, x: f32, y: f32, viewport: &Viewport) -> HitResult {
+        self.pointer_down_with_id(0, x, y, viewport)
+    }
+    /// Same as [`Scene::pointer_move_with_id`] with a pointer id of `0`.
+    #[inline]
+    fn pointer_move(&mut self, x: f32, y: f32, viewport: &Viewport) -> HitResult {
+        self.pointer_move_with_id(0, x, y, viewport)
+    }
+    /// Same as [`Scene::pointer_up_with_id`] with a pointer id of `0`.
+    #[inline]
+    fn pointer_up(&mut self, x: f32, y: f32, viewport: &Viewport) -> HitResult {
+        self.pointer_up_with_id(0, x, y, viewport)
+    }
+    /// Same as [`Scene::pointer_exit_with_id`] with a pointer id of `0`.
+    #[inline]
+    fn pointer_exit(&mut self, x: f32, y: f32, viewport: &Viewport) -> HitResult {
+        self.pointer_exit_with_id(0, x, y, viewport)
+    }
+    /// Presses pointer `pointer_id` at viewport coordinates `x`, `y`. Every finger of a
+    /// multi-touch gesture should use its own id.
+    fn pointer_down_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult;
+    fn pointer_move_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult;
+    fn pointer_up_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult;
+    /// Notifies the scene that pointer `pointer_id` left the viewport at `x`, `y`, e.g. so that
+    /// hover states are reset.
+    fn pointer_exit_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult;
     fn advance_and_apply(&mut self, elapsed: Duration) -> bool;
     fn draw(&self, renderer: &mut R);
     fn advance_and_maybe_draw(
@@ -72,7 +254,7 @@ pub trait Scene<R: Renderer>: Send + Sync {
 }
 
 macro_rules! impl_scene {
-    ( $type:tt ) => {
+    ( $type:tt $( , $on_advance:ident )? ) => {
         impl<R: Renderer> crate::scene::Scene<R> for $type<R> {
             fn as_any(&self) -> &dyn ::core::any::Any {
                 self
@@ -124,49 +306,93 @@ macro_rules! impl_scene {
             }
 
             #[inline]
-            fn pointer_down(&mut self, x: f32, y: f32, viewport: &crate::scene::Viewport) {
-                let [x, y] = crate::scene::transform(x, y, &viewport.inverse_view_transform);
-                unsafe {
-                    crate::ffi::rive_rs_scene_pointer_down(self.raw_scene(), x, y);
-                }
+            fn hit_test(&self, x: f32, y: f32, viewport: &crate::scene::Viewport) -> bool {
+                let (_, inverse_view_transform) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+                let [x, y] = crate::scene::transform(x, y, &inverse_view_transform);
+                self.raw_hit_test(x, y)
             }
 
             #[inline]
-            fn pointer_move(&mut self, x: f32, y: f32, viewport: &crate::scene::Viewport) {
-                let [x, y] = crate::scene::transform(x, y, &viewport.inverse_view_transform);
-                unsafe {
-                    crate::ffi::rive_rs_scene_pointer_move(self.raw_scene(), x, y);
-                }
+            fn pointer_down_with_id(
+                &mut self,
+                pointer_id: i32,
+                x: f32,
+                y: f32,
+                viewport: &crate::scene::Viewport,
+            ) -> crate::scene::HitResult {
+                let (_, inverse_view_transform) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+                let [x, y] = crate::scene::transform(x, y, &inverse_view_transform);
+                unsafe { crate::ffi::rive_rs_scene_pointer_down(self.raw_scene(), pointer_id, x, y) }
             }
 
             #[inline]
-            fn pointer_up(&mut self, x: f32, y: f32, viewport: &crate::scene::Viewport) {
-                let [x, y] = crate::scene::transform(x, y, &viewport.inverse_view_transform);
-                unsafe {
-                    crate::ffi::rive_rs_scene_pointer_up(self.raw_scene(), x, y);
-                }
+            fn pointer_move_with_id(
+                &mut self,
+                pointer_id: i32,
+                x: f32,
+                y: f32,
+                viewport: &crate::scene::Viewport,
+            ) -> crate::scene::HitResult {
+                let (_, inverse_view_transform) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+                let [x, y] = crate::scene::transform(x, y, &inverse_view_transform);
+                unsafe { crate::ffi::rive_rs_scene_pointer_move(self.raw_scene(), pointer_id, x, y) }
+            }
+
+            #[inline]
+            fn pointer_up_with_id(
+                &mut self,
+                pointer_id: i32,
+                x: f32,
+                y: f32,
+                viewport: &crate::scene::Viewport,
+            ) -> crate::scene::HitResult {
+                let (_, inverse_view_transform) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+                let [x, y] = crate::scene::transform(x, y, &inverse_view_transform);
+                unsafe { crate::ffi::rive_rs_scene_pointer_up(self.raw_scene(), pointer_id, x, y) }
+            }
+
+            #[inline]
+            fn pointer_exit_with_id(
+                &mut self,
+                pointer_id: i32,
+                x: f32,
+                y: f32,
+                viewport: &crate::scene::Viewport,
+            ) -> crate::scene::HitResult {
+                let (_, inverse_view_transform) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+                let [x, y] = crate::scene::transform(x, y, &inverse_view_transform);
+                unsafe { crate::ffi::rive_rs_scene_pointer_exit(self.raw_scene(), pointer_id, x, y) }
             }
 
             #[inline]
             fn advance_and_apply(&mut self, elapsed: ::core::time::Duration) -> bool {
-                unsafe {
+                let keep_going = self.artboard.assets_lock().read(|| unsafe {
                     crate::ffi::rive_rs_scene_advance_and_apply(
                         self.raw_scene(),
                         elapsed.as_secs_f32(),
                     )
-                }
+                });
+
+                $( self.$on_advance(); )?
+
+                keep_going
             }
 
             #[inline]
             fn draw(&self, renderer: &mut R) {
-                unsafe {
+                self.artboard.assets_lock().read(|| unsafe {
                     crate::ffi::rive_rs_scene_draw(
                         self.raw_scene(),
                         renderer as *mut R as *mut (),
                         crate::ffi::RendererEntries::<R>::ENTRIES
                             as *const crate::ffi::RendererEntries<R> as *const (),
                     );
-                }
+                });
             }
 
             #[inline]
@@ -176,25 +402,15 @@ macro_rules! impl_scene {
                 elapsed: ::core::time::Duration,
                 viewport: &mut crate::scene::Viewport,
             ) -> bool {
-                let mut view_transform = [0.0; 6];
-                let mut inverse_view_transform = [0.0; 6];
-
-                unsafe {
-                    crate::ffi::rive_rs_artboard_instance_transforms(
-                        self.raw_artboard(),
-                        viewport.width,
-                        viewport.height,
-                        view_transform.as_mut_ptr(),
-                        inverse_view_transform.as_mut_ptr(),
-                    );
-                }
-
-                viewport.inverse_view_transform = inverse_view_transform;
+                crate::scene::resize_to_viewport(self.raw_artboard(), viewport);
 
                 if !self.advance_and_apply(elapsed) {
                     return false;
                 }
 
+                let (view_transform, _) =
+                    crate::scene::view_transforms(self.raw_artboard(), viewport);
+
                 renderer.state_push();
                 renderer.transform(&view_transform);
 
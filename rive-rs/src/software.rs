@@ -0,0 +1,830 @@
+//! A CPU-only [`Renderer`](renderer::Renderer) back-end based on [tiny-skia], useful to render
+//! frames where no GPU is available, e.g. for golden-image tests or thumbnails.
+//!
+//! [tiny-skia]: https://github.com/RazrFalcon/tiny-skia
+
+use std::{cell::OnceCell, fmt, io::Cursor, sync::Arc};
+
+use image::io::Reader;
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, IntSize, LineCap, LineJoin, LinearGradient, Mask,
+    Pattern, Pixmap, PixmapPaint, Point, RadialGradient, Shader, SpreadMode, Stroke, Transform,
+};
+
+use crate::{renderer, util};
+
+fn to_skia_color(color: renderer::Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+fn to_skia_filter_quality(filter: renderer::ImageFilter) -> FilterQuality {
+    match filter {
+        renderer::ImageFilter::Bilinear | renderer::ImageFilter::Mipmap => FilterQuality::Bilinear,
+        renderer::ImageFilter::Nearest => FilterQuality::Nearest,
+    }
+}
+
+fn to_skia_spread_mode(wrap: renderer::ImageWrap) -> SpreadMode {
+    match wrap {
+        renderer::ImageWrap::Clamp => SpreadMode::Pad,
+        renderer::ImageWrap::Repeat => SpreadMode::Repeat,
+        renderer::ImageWrap::Mirror => SpreadMode::Reflect,
+    }
+}
+
+fn to_skia_blend_mode(blend_mode: renderer::BlendMode) -> tiny_skia::BlendMode {
+    match blend_mode {
+        renderer::BlendMode::SrcOver => tiny_skia::BlendMode::SourceOver,
+        renderer::BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        renderer::BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+        renderer::BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        renderer::BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        renderer::BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
+        renderer::BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
+        renderer::BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+        renderer::BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
+        renderer::BlendMode::Difference => tiny_skia::BlendMode::Difference,
+        renderer::BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+        renderer::BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        renderer::BlendMode::Hue => tiny_skia::BlendMode::Hue,
+        renderer::BlendMode::Saturation => tiny_skia::BlendMode::Saturation,
+        renderer::BlendMode::Color => tiny_skia::BlendMode::Color,
+        renderer::BlendMode::Luminosity => tiny_skia::BlendMode::Luminosity,
+    }
+}
+
+fn to_skia_transform(transform: &[f32; 6]) -> Transform {
+    let [sx, ky, kx, sy, tx, ty] = *transform;
+    Transform::from_row(sx, ky, kx, sy, tx, ty)
+}
+
+fn polygon_path(points: impl IntoIterator<Item = [f32; 2]>) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+
+    for (i, point) in points.into_iter().enumerate() {
+        if i == 0 {
+            builder.move_to(point[0], point[1]);
+        } else {
+            builder.line_to(point[0], point[1]);
+        }
+    }
+    builder.close();
+
+    builder.finish()
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    inner: Vec<u8>,
+}
+
+impl Buffer {
+    #[inline]
+    pub fn as_f32_pairs(&self) -> &[[f32; 2]] {
+        bytemuck::cast_slice(&self.inner)
+    }
+
+    #[inline]
+    pub fn as_u16s(&self) -> &[u16] {
+        bytemuck::cast_slice(&self.inner)
+    }
+}
+
+impl renderer::Buffer for Buffer {
+    #[inline]
+    fn new(
+        _type: renderer::BufferType,
+        _flags: renderer::BufferFlags,
+        len_in_bytes: usize,
+    ) -> Self {
+        Self {
+            inner: vec![0; len_in_bytes],
+        }
+    }
+
+    #[inline]
+    fn map(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn unmap(&mut self) {}
+}
+
+#[derive(Debug, Default)]
+pub struct Path {
+    builder: tiny_skia::PathBuilder,
+    fill_rule: FillRule,
+    // `tiny_skia::Path` is immutable and needs to be rebuilt after every change, so it's only
+    // built lazily the first time it's drawn.
+    path: OnceCell<Option<tiny_skia::Path>>,
+}
+
+impl Path {
+    fn path(&self) -> Option<&tiny_skia::Path> {
+        self.path
+            .get_or_init(|| self.builder.clone().finish())
+            .as_ref()
+    }
+
+    #[inline]
+    fn invalidate(&mut self) {
+        self.path.take();
+    }
+}
+
+impl renderer::Path for Path {
+    fn new(commands: &mut crate::path::Commands, fill_rule: crate::path::FillRule) -> Self {
+        let mut path = Self::default();
+
+        for (verb, points) in commands {
+            match verb {
+                crate::path::Verb::Move => path.move_to(points[0].x, points[0].y),
+                crate::path::Verb::Line => path.line_to(points[0].x, points[0].y),
+                crate::path::Verb::Cubic => path.cubic_to(
+                    points[0].x,
+                    points[0].y,
+                    points[1].x,
+                    points[1].y,
+                    points[2].x,
+                    points[2].y,
+                ),
+                crate::path::Verb::Close => path.close(),
+            }
+        }
+
+        path.set_fill_rule(fill_rule);
+
+        path
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.builder.clear();
+        self.invalidate();
+    }
+
+    #[inline]
+    fn extend(&mut self, from: &Self, transform: &[f32; 6]) {
+        if let Some(from) = from
+            .path()
+            .and_then(|path| path.clone().transform(to_skia_transform(transform)))
+        {
+            self.builder.push_path(&from);
+            self.invalidate();
+        }
+    }
+
+    #[inline]
+    fn set_fill_rule(&mut self, fill_rule: crate::path::FillRule) {
+        self.fill_rule = match fill_rule {
+            crate::path::FillRule::NonZero => FillRule::Winding,
+            crate::path::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+    }
+
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x, y);
+        self.invalidate();
+    }
+
+    #[inline]
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x, y);
+        self.invalidate();
+    }
+
+    #[inline]
+    fn cubic_to(&mut self, ox: f32, oy: f32, ix: f32, iy: f32, x: f32, y: f32) {
+        self.builder.cubic_to(ox, oy, ix, iy, x, y);
+        self.invalidate();
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.builder.close();
+        self.invalidate();
+    }
+}
+
+#[derive(Debug)]
+pub struct Paint {
+    style: renderer::PaintStyle,
+    stroke: Stroke,
+    shader: Shader<'static>,
+    blend_mode: tiny_skia::BlendMode,
+}
+
+impl Default for Paint {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            style: renderer::PaintStyle::Fill,
+            stroke: Stroke::default(),
+            shader: Shader::SolidColor(tiny_skia::Color::TRANSPARENT),
+            blend_mode: tiny_skia::BlendMode::SourceOver,
+        }
+    }
+}
+
+impl renderer::Paint for Paint {
+    type Gradient = Gradient;
+
+    #[inline]
+    fn set_style(&mut self, style: renderer::PaintStyle) {
+        self.style = style;
+    }
+
+    #[inline]
+    fn set_color(&mut self, color: renderer::Color) {
+        self.shader = Shader::SolidColor(to_skia_color(color));
+    }
+
+    #[inline]
+    fn set_thickness(&mut self, thickness: f32) {
+        self.stroke.width = thickness;
+    }
+
+    #[inline]
+    fn set_join(&mut self, join: renderer::StrokeJoin) {
+        self.stroke.line_join = match join {
+            renderer::StrokeJoin::Miter => LineJoin::Miter,
+            renderer::StrokeJoin::Round => LineJoin::Round,
+            renderer::StrokeJoin::Bevel => LineJoin::Bevel,
+        };
+    }
+
+    #[inline]
+    fn set_cap(&mut self, cap: renderer::StrokeCap) {
+        self.stroke.line_cap = match cap {
+            renderer::StrokeCap::Butt => LineCap::Butt,
+            renderer::StrokeCap::Round => LineCap::Round,
+            renderer::StrokeCap::Square => LineCap::Square,
+        };
+    }
+
+    #[inline]
+    fn set_blend_mode(&mut self, blend_mode: renderer::BlendMode) {
+        self.blend_mode = to_skia_blend_mode(blend_mode);
+    }
+
+    #[inline]
+    fn set_gradient(&mut self, gradient: &Self::Gradient) {
+        self.shader = gradient.inner.clone();
+    }
+
+    #[inline]
+    fn invalidate_stroke(&mut self) {}
+}
+
+#[derive(Debug)]
+pub struct Gradient {
+    inner: Shader<'static>,
+}
+
+impl Gradient {
+    fn new(
+        colors: &[renderer::Color],
+        stops: &[f32],
+        new_shader: impl FnOnce(Vec<GradientStop>) -> Option<Shader<'static>>,
+    ) -> Self {
+        let gradient_stops = colors
+            .iter()
+            .zip(stops.iter())
+            .map(|(&color, &offset)| GradientStop::new(offset, to_skia_color(color)))
+            .collect();
+
+        // tiny-skia refuses to create degenerate gradients, e.g. with coincident end points. Fall
+        // back to the first color like other Rive renderers do.
+        let inner = new_shader(gradient_stops).unwrap_or_else(|| {
+            Shader::SolidColor(
+                colors
+                    .first()
+                    .copied()
+                    .map(to_skia_color)
+                    .unwrap_or(tiny_skia::Color::TRANSPARENT),
+            )
+        });
+
+        Self { inner }
+    }
+}
+
+impl renderer::Gradient for Gradient {
+    #[inline]
+    fn new_linear(
+        sx: f32,
+        sy: f32,
+        ex: f32,
+        ey: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(colors, stops, |stops| {
+            LinearGradient::new(
+                Point::from_xy(sx, sy),
+                Point::from_xy(ex, ey),
+                stops,
+                SpreadMode::Pad,
+                Transform::identity(),
+            )
+        })
+    }
+
+    #[inline]
+    fn new_radial(
+        cx: f32,
+        cy: f32,
+        radius: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(colors, stops, |stops| {
+            RadialGradient::new(
+                Point::from_xy(cx, cy),
+                Point::from_xy(cx, cy),
+                radius,
+                stops,
+                SpreadMode::Pad,
+                Transform::identity(),
+            )
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Image {
+    inner: Pixmap,
+}
+
+impl renderer::Image for Image {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let image = Reader::new(Cursor::new(data))
+            .with_guessed_format()
+            .ok()?
+            .decode()
+            .ok()?
+            .into_rgba8();
+        let size = IntSize::from_wh(image.width(), image.height())?;
+
+        let mut data = image.into_raw();
+        for pixel in data.chunks_exact_mut(4) {
+            let alpha = pixel[3] as u16;
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u16 * alpha + 127) / 255) as u8;
+            }
+        }
+
+        Some(Image {
+            inner: Pixmap::from_vec(data, size)?,
+        })
+    }
+}
+
+#[derive(Clone, Default)]
+struct State {
+    transform: Transform,
+    clip: Option<Arc<Mask>>,
+}
+
+/// Renders into an offscreen RGBA [`Pixmap`] of a fixed size.
+pub struct Renderer {
+    pixmap: Pixmap,
+    states: Vec<State>,
+}
+
+impl Renderer {
+    /// Creates a renderer with a transparent `width`x`height` target. Returns `None` if either
+    /// dimension is zero.
+    pub fn new(width: u32, height: u32) -> Option<Self> {
+        Some(Self {
+            pixmap: Pixmap::new(width, height)?,
+            states: vec![State::default()],
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    /// Fills the whole target with `color`, e.g. before drawing a new frame.
+    #[inline]
+    pub fn clear(&mut self, color: renderer::Color) {
+        self.pixmap.fill(to_skia_color(color));
+    }
+
+    /// Target with premultiplied alpha.
+    #[inline]
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    #[inline]
+    pub fn into_pixmap(self) -> Pixmap {
+        self.pixmap
+    }
+
+    /// Copies the target into a row-major RGBA buffer with straight (non-premultiplied) alpha.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect()
+    }
+
+    fn last_state(&mut self) -> &mut State {
+        self.states.last_mut().unwrap()
+    }
+}
+
+impl renderer::Renderer for Renderer {
+    type Buffer = Buffer;
+
+    type Path = Path;
+
+    type Paint = Paint;
+
+    type Gradient = Gradient;
+
+    type Image = Image;
+
+    #[inline]
+    fn state_push(&mut self) {
+        let last_state = self.last_state().clone();
+        self.states.push(last_state);
+    }
+
+    #[inline]
+    fn state_pop(&mut self) {
+        self.states.pop();
+
+        if self.states.is_empty() {
+            self.states.push(State::default());
+        }
+    }
+
+    #[inline]
+    fn transform(&mut self, transform: &[f32; 6]) {
+        let last_state = self.last_state();
+        last_state.transform = last_state
+            .transform
+            .pre_concat(to_skia_transform(transform));
+    }
+
+    fn set_clip(&mut self, path: &Self::Path) {
+        // Clips are intersected with every clip set before them, both at the same level and at
+        // the enclosing ones, which `state_push` copies into the current level.
+        let (width, height) = (self.width(), self.height());
+        let last_state = self.last_state();
+        let transform = last_state.transform;
+
+        let Some(skia_path) = path.path() else {
+            // An empty clip path hides everything.
+            last_state.clip = Mask::new(width, height).map(Arc::new);
+            return;
+        };
+
+        let mask = match last_state.clip.take() {
+            Some(clip) => {
+                let mut mask = Arc::try_unwrap(clip).unwrap_or_else(|clip| Mask::clone(&clip));
+                mask.intersect_path(skia_path, path.fill_rule, true, transform);
+                Some(mask)
+            }
+            None => Mask::new(width, height).map(|mut mask| {
+                mask.fill_path(skia_path, path.fill_rule, true, transform);
+                mask
+            }),
+        };
+
+        last_state.clip = mask.map(Arc::new);
+    }
+
+    fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint) {
+        let Some(skia_path) = path.path() else {
+            return;
+        };
+
+        let State { transform, clip } = self.states.last().unwrap();
+        let skia_paint = tiny_skia::Paint {
+            shader: paint.shader.clone(),
+            blend_mode: paint.blend_mode,
+            ..Default::default()
+        };
+
+        match paint.style {
+            renderer::PaintStyle::Fill => self.pixmap.fill_path(
+                skia_path,
+                &skia_paint,
+                path.fill_rule,
+                *transform,
+                clip.as_deref(),
+            ),
+            renderer::PaintStyle::Stroke => self.pixmap.stroke_path(
+                skia_path,
+                &skia_paint,
+                &paint.stroke,
+                *transform,
+                clip.as_deref(),
+            ),
+        }
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let image = &image.inner;
+        let State { transform, clip } = self.states.last().unwrap();
+
+        let transform =
+            transform.pre_translate(image.width() as f32 * -0.5, image.height() as f32 * -0.5);
+
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            image.as_ref(),
+            &PixmapPaint {
+                opacity,
+                blend_mode: to_skia_blend_mode(blend_mode),
+                quality: to_skia_filter_quality(sampler.filter),
+            },
+            transform,
+            clip.as_deref(),
+        );
+    }
+
+    fn draw_image_mesh(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        vertices: &Self::Buffer,
+        uvs: &Self::Buffer,
+        indices: &Self::Buffer,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let image = &image.inner;
+        let vertices = vertices.as_f32_pairs();
+        let uvs = uvs.as_f32_pairs();
+        let blend_mode = to_skia_blend_mode(blend_mode);
+        let quality = to_skia_filter_quality(sampler.filter);
+
+        let State { transform, clip } = self.states.last().unwrap();
+
+        // Aliased rasterization covers every pixel of shared triangle edges exactly once,
+        // so the mesh has neither seams nor overdraw.
+        let mut fill = |path: tiny_skia::Path, spread_mode, pattern_transform| {
+            let paint = tiny_skia::Paint {
+                shader: Pattern::new(
+                    image.as_ref(),
+                    spread_mode,
+                    quality,
+                    opacity,
+                    pattern_transform,
+                ),
+                blend_mode,
+                anti_alias: false,
+                ..Default::default()
+            };
+
+            self.pixmap.fill_path(
+                &path,
+                &paint,
+                FillRule::Winding,
+                *transform,
+                clip.as_deref(),
+            );
+        };
+
+        for triangle_indices in indices.as_u16s().chunks_exact(3) {
+            let points = [
+                vertices[triangle_indices[0] as usize],
+                vertices[triangle_indices[1] as usize],
+                vertices[triangle_indices[2] as usize],
+            ];
+            let uvs = [
+                uvs[triangle_indices[0] as usize],
+                uvs[triangle_indices[1] as usize],
+                uvs[triangle_indices[2] as usize],
+            ];
+
+            if sampler.wrap_x == sampler.wrap_y {
+                let Some(path) = polygon_path(points) else {
+                    continue;
+                };
+
+                let pattern_transform =
+                    util::map_uvs_to_triangle(&points, &uvs, image.width(), image.height());
+
+                fill(
+                    path,
+                    to_skia_spread_mode(sampler.wrap_x),
+                    to_skia_transform(&pattern_transform),
+                );
+
+                continue;
+            }
+
+            // tiny-skia patterns have a single spread mode for both axes, so triangles are drawn
+            // one repetition of the image at a time when the axes wrap differently.
+            let uv_transform = to_skia_transform(&util::map_uvs_to_triangle(&points, &uvs, 1, 1));
+            let [(x_cuts, x_cells), (y_cuts, y_cells)] = [0, 1].map(|axis| {
+                let wrap = [sampler.wrap_x, sampler.wrap_y][axis];
+                let (start, end) = uvs
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(start, end), uv| {
+                        (start.min(uv[axis]), end.max(uv[axis]))
+                    });
+
+                util::uv_cells(wrap, start, end, 1.0, 1)
+            });
+
+            util::split_triangle(
+                points,
+                uvs,
+                [false; 3],
+                [&x_cuts, &y_cuts],
+                |[x, y], part| {
+                    let Some(path) = polygon_path(part.iter().map(|vertex| vertex.point)) else {
+                        return;
+                    };
+
+                    // Maps the pixels of the image to UV space, one axis at a time.
+                    let [(x_scale, x_offset), (y_scale, y_offset)] =
+                        [(x_cells[x], image.width()), (y_cells[y], image.height())].map(
+                            |(cell, size)| {
+                                if cell.mirrored {
+                                    (-(size as f32).recip(), cell.tile + 1.0)
+                                } else {
+                                    ((size as f32).recip(), cell.tile)
+                                }
+                            },
+                        );
+
+                    fill(
+                        path,
+                        SpreadMode::Pad,
+                        uv_transform.pre_concat(Transform::from_row(
+                            x_scale, 0.0, 0.0, y_scale, x_offset, y_offset,
+                        )),
+                    );
+                },
+            );
+        }
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let transforms: Vec<_> = self.states.iter().map(|state| state.transform).collect();
+        let clips: Vec<_> = self
+            .states
+            .iter()
+            .map(|state| state.clip.is_some())
+            .collect();
+
+        f.debug_struct("Renderer")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("transforms", &transforms)
+            .field("clips", &clips)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::renderer::{Paint as _, Path as _, Renderer as _};
+
+    use super::*;
+
+    const RED: renderer::Color = renderer::Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn rect([x0, y0, x1, y1]: [f32; 4]) -> Path {
+        let mut path = Path::default();
+        path.move_to(x0, y0);
+        path.line_to(x1, y0);
+        path.line_to(x1, y1);
+        path.line_to(x0, y1);
+        path.close();
+
+        path
+    }
+
+    fn is_painted(renderer: &Renderer, x: u32, y: u32) -> bool {
+        renderer.pixmap().pixel(x, y).unwrap().alpha() != 0
+    }
+
+    #[test]
+    fn clips_at_the_same_level_intersect() {
+        let mut renderer = Renderer::new(10, 10).unwrap();
+        let mut paint = Paint::default();
+        paint.set_color(RED);
+
+        renderer.set_clip(&rect([0.0, 0.0, 6.0, 10.0]));
+        renderer.set_clip(&rect([4.0, 0.0, 10.0, 10.0]));
+        renderer.draw_path(&rect([0.0, 0.0, 10.0, 10.0]), &paint);
+
+        assert!(!is_painted(&renderer, 2, 5));
+        assert!(is_painted(&renderer, 5, 5));
+        assert!(!is_painted(&renderer, 8, 5));
+    }
+
+    #[test]
+    fn clips_are_dropped_with_their_level() {
+        let mut renderer = Renderer::new(10, 10).unwrap();
+        let mut paint = Paint::default();
+        paint.set_color(RED);
+
+        renderer.set_clip(&rect([0.0, 0.0, 6.0, 10.0]));
+        renderer.state_push();
+        renderer.set_clip(&rect([4.0, 0.0, 10.0, 10.0]));
+        renderer.state_pop();
+        renderer.draw_path(&rect([0.0, 0.0, 10.0, 10.0]), &paint);
+
+        assert!(is_painted(&renderer, 2, 5));
+        assert!(!is_painted(&renderer, 8, 5));
+    }
+
+    fn draw_wrapped_quad(wrap_x: renderer::ImageWrap, wrap_y: renderer::ImageWrap) -> Renderer {
+        // Red, green, blue and white pixels.
+        let image = Image {
+            inner: Pixmap::from_vec(
+                vec![
+                    255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 255, 255, 255, 255,
+                ],
+                IntSize::from_wh(2, 2).unwrap(),
+            )
+            .unwrap(),
+        };
+        let buffer = |bytes: &[u8]| Buffer {
+            inner: bytes.to_vec(),
+        };
+
+        let vertices: [[f32; 2]; 4] = [[0.0, 0.0], [20.0, 0.0], [20.0, 20.0], [0.0, 20.0]];
+        let uvs: [[f32; 2]; 4] = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+        let mut renderer = Renderer::new(20, 20).unwrap();
+        renderer.draw_image_mesh(
+            &image,
+            renderer::ImageSampler {
+                wrap_x,
+                wrap_y,
+                filter: renderer::ImageFilter::Nearest,
+            },
+            &buffer(bytemuck::cast_slice(&vertices)),
+            &buffer(bytemuck::cast_slice(&uvs)),
+            &buffer(bytemuck::cast_slice(&indices)),
+            renderer::BlendMode::SrcOver,
+            1.0,
+        );
+
+        renderer
+    }
+
+    fn rgb(renderer: &Renderer, x: u32, y: u32) -> [u8; 3] {
+        let pixel = renderer.pixmap().pixel(x, y).unwrap();
+        [pixel.red(), pixel.green(), pixel.blue()]
+    }
+
+    #[test]
+    fn axes_wrap_separately() {
+        let renderer = draw_wrapped_quad(renderer::ImageWrap::Clamp, renderer::ImageWrap::Repeat);
+
+        assert_eq!(rgb(&renderer, 2, 12), [255, 0, 0]);
+        assert_eq!(rgb(&renderer, 12, 2), [0, 255, 0]);
+        assert_eq!(rgb(&renderer, 17, 17), [255, 255, 255]);
+
+        let renderer = draw_wrapped_quad(renderer::ImageWrap::Mirror, renderer::ImageWrap::Clamp);
+
+        assert_eq!(rgb(&renderer, 12, 2), [0, 255, 0]);
+        assert_eq!(rgb(&renderer, 17, 2), [255, 0, 0]);
+        assert_eq!(rgb(&renderer, 2, 12), [0, 0, 255]);
+        assert_eq!(rgb(&renderer, 2, 17), [0, 0, 255]);
+
+        // Parts of the triangles share their edges exactly, so no pixel is left out.
+        assert!(
+            (0..20).all(|y| (0..20).all(|x| renderer.pixmap().pixel(x, y).unwrap().alpha() == 255))
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::{collections::BTreeMap, string::String};
+use alloc::{boxed::Box, collections::BTreeMap, string::String};
 use core::{ptr, time::Duration};
 
 use crate::{
@@ -11,12 +11,57 @@ mod properties;
 pub use properties::Property;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Event {
     pub name: String,
     pub delay: Duration,
     pub properties: BTreeMap<String, Property>,
 }
 
+#[cfg(feature = "serde")]
+impl Event {
+    /// Deserializes the custom properties of the event into `T`, as if they were the fields of
+    /// a struct. Numbers are deserialized as floating point values.
+    pub fn deserialize_properties<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<T, serde::de::value::Error> {
+        use serde::de::value::MapDeserializer;
+
+        let deserializer = MapDeserializer::new(
+            self.properties
+                .iter()
+                .map(|(name, property)| (name.as_str(), property)),
+        );
+
+        T::deserialize(deserializer)
+    }
+}
+
+/// Identifies a listener registered with [`StateMachine::on_event`] or
+/// [`StateMachine::on_any_event`].
+///
+/// [`StateMachine::on_event`]: crate::state_machine::StateMachine::on_event
+/// [`StateMachine::on_any_event`]: crate::state_machine::StateMachine::on_any_event
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ListenerId(pub(crate) u64);
+
+pub(crate) struct Listener {
+    pub id: ListenerId,
+    /// Listens to all events when `None`.
+    pub name: Option<String>,
+    pub callback: Box<dyn FnMut(&Event) + Send>,
+}
+
+impl Listener {
+    #[inline]
+    pub fn matches(&self, event: &Event) -> bool {
+        match &self.name {
+            Some(name) => *name == event.name,
+            None => true,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct RawStateMachine(pub *mut ffi::StateMachine);
 
@@ -1,8 +1,85 @@
 use alloc::string::String;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Property {
     Bool(bool),
     Number(f32),
     String(String),
 }
+
+impl Property {
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_number(&self) -> Option<f32> {
+        match *self {
+            Self::Number(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod de {
+    use core::marker::PhantomData;
+
+    use serde::de::{self, IntoDeserializer, Visitor};
+
+    use super::Property;
+
+    pub struct PropertyDeserializer<'p, E> {
+        property: &'p Property,
+        _phantom: PhantomData<E>,
+    }
+
+    impl<'de, 'p, E: de::Error> IntoDeserializer<'de, E> for &'p Property {
+        type Deserializer = PropertyDeserializer<'p, E>;
+
+        #[inline]
+        fn into_deserializer(self) -> Self::Deserializer {
+            PropertyDeserializer {
+                property: self,
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, 'p, E: de::Error> de::Deserializer<'de> for PropertyDeserializer<'p, E> {
+        type Error = E;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+            match self.property {
+                Property::Bool(val) => visitor.visit_bool(*val),
+                Property::Number(val) => visitor.visit_f32(*val),
+                Property::String(val) => visitor.visit_str(val),
+            }
+        }
+
+        // Properties are always present, so optional fields are `Some`.
+        #[inline]
+        fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+            visitor.visit_some(self)
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::{string::String, vec::Vec};
 use core::{fmt, marker::PhantomData, ptr, slice, str};
 
 use crate::{
@@ -123,6 +124,15 @@ impl<'s> fmt::Debug for Trigger<'s> {
     }
 }
 
+/// Value of a state machine input. [`Trigger`](InputValue::Trigger)s carry no value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum InputValue {
+    Bool(bool),
+    Number(f32),
+    Trigger,
+}
+
 #[derive(Debug)]
 pub enum Input<'s> {
     Bool(Bool<'s>),
@@ -130,6 +140,46 @@ pub enum Input<'s> {
     Trigger(Trigger<'s>),
 }
 
+impl Input<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Bool(bool) => bool.name(),
+            Self::Number(number) => number.name(),
+            Self::Trigger(trigger) => trigger.name(),
+        }
+    }
+
+    pub fn value(&self) -> InputValue {
+        match self {
+            Self::Bool(bool) => InputValue::Bool(bool.get()),
+            Self::Number(number) => InputValue::Number(number.get()),
+            Self::Trigger(_) => InputValue::Trigger,
+        }
+    }
+}
+
+/// Value of a named state machine input, as captured by an [`InputSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InputState {
+    pub name: String,
+    pub value: InputValue,
+}
+
+/// Values of all the inputs of a state machine, in the order of the state machine's inputs.
+///
+/// Snapshots are taken with [`StateMachine::input_snapshot`] and can be restored onto any
+/// instance of a state machine with inputs of the same names with
+/// [`StateMachine::restore_inputs`].
+///
+/// [`StateMachine::input_snapshot`]: crate::state_machine::StateMachine::input_snapshot
+/// [`StateMachine::restore_inputs`]: crate::state_machine::StateMachine::restore_inputs
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InputSnapshot {
+    pub inputs: Vec<InputState>,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct RawStateMachine(pub *mut ffi::StateMachine);
 
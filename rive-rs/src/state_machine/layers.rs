@@ -0,0 +1,269 @@
+use core::{fmt, marker::PhantomData, ptr, slice, str, time::Duration};
+
+use crate::{
+    ffi,
+    raw_iter::{impl_iter, Raw},
+};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StateKind {
+    /// The state every layer starts in.
+    Entry,
+    /// The state a layer ends up in once it is done.
+    Exit,
+    /// The state whose transitions can be taken from any other state.
+    Any,
+    /// Plays a single animation.
+    Animation,
+    /// Blends multiple animations together.
+    Blend,
+    Unknown,
+}
+
+/// A state of a state machine layer. States compare equal when they are the same state of the
+/// same layer.
+#[derive(Clone, Copy)]
+pub struct LayerState<'s> {
+    raw_state: *const ffi::LayerState,
+    _phantom: PhantomData<&'s ()>,
+}
+
+impl<'s> LayerState<'s> {
+    pub(crate) fn new(raw_state: *const ffi::LayerState) -> Self {
+        Self {
+            raw_state,
+            _phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> StateKind {
+        unsafe { ffi::rive_rs_layer_state_kind(self.raw_state) }
+    }
+
+    /// Returns the name of the animation played by [`StateKind::Animation`] states. Other states
+    /// don't have a name.
+    pub fn name(&self) -> Option<&'s str> {
+        let mut data: *const u8 = ptr::null();
+        let mut len = 0;
+
+        unsafe {
+            ffi::rive_rs_layer_state_name(
+                self.raw_state,
+                &mut data as *mut *const u8,
+                &mut len as *mut usize,
+            );
+        }
+
+        (!data.is_null()).then(|| {
+            let bytes = unsafe { slice::from_raw_parts(data, len) };
+            str::from_utf8(bytes).expect("state name is invalid UTF-8")
+        })
+    }
+}
+
+impl PartialEq for LayerState<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.raw_state == other.raw_state
+    }
+}
+
+impl Eq for LayerState<'_> {}
+
+impl fmt::Debug for LayerState<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayerState")
+            .field("kind", &self.kind())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+/// An animation that is currently being played by the state machine.
+#[derive(Clone, Copy)]
+pub struct PlayingAnimation<'s> {
+    raw_linear_animation: *const ffi::LinearAnimation,
+    _phantom: PhantomData<&'s ()>,
+}
+
+impl<'s> PlayingAnimation<'s> {
+    pub fn name(&self) -> &'s str {
+        let mut data = ptr::null();
+        let mut len = 0;
+
+        let bytes = unsafe {
+            ffi::rive_rs_linear_animation_name(
+                self.raw_linear_animation,
+                &mut data as *mut *const u8,
+                &mut len as *mut usize,
+            );
+            slice::from_raw_parts(data, len)
+        };
+
+        str::from_utf8(bytes).expect("animation name is invalid UTF-8")
+    }
+
+    #[inline]
+    pub fn time(&self) -> Duration {
+        Duration::from_secs_f32(unsafe {
+            ffi::rive_rs_linear_animation_time(self.raw_linear_animation as *mut _)
+        })
+    }
+}
+
+impl fmt::Debug for PlayingAnimation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlayingAnimation")
+            .field("name", &self.name())
+            .field("time", &self.time())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawPlayingAnimations(pub *mut ffi::StateMachine);
+
+impl Raw for RawPlayingAnimations {
+    type Item<'s> = PlayingAnimation<'s>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_state_machine_playing_animation_count(self.0) }
+    }
+
+    unsafe fn get<'s>(self, index: usize) -> Self::Item<'s> {
+        PlayingAnimation {
+            raw_linear_animation: ffi::rive_rs_state_machine_playing_animation_at(self.0, index),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl_iter!(PlayingAnimations, PlayingAnimation, RawPlayingAnimations, 's);
+
+pub struct Layer<'s> {
+    raw_state_machine: *mut ffi::StateMachine,
+    index: usize,
+    current_state: LayerState<'s>,
+}
+
+impl<'s> Layer<'s> {
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &'s str {
+        let mut data = ptr::null();
+        let mut len = 0;
+
+        let bytes = unsafe {
+            ffi::rive_rs_state_machine_layer_name(
+                self.raw_state_machine,
+                self.index,
+                &mut data as *mut *const u8,
+                &mut len as *mut usize,
+            );
+            slice::from_raw_parts(data, len)
+        };
+
+        str::from_utf8(bytes).expect("layer name is invalid UTF-8")
+    }
+
+    /// Returns the state the layer is in as of the last advance.
+    #[inline]
+    pub fn current_state(&self) -> LayerState<'s> {
+        self.current_state
+    }
+
+    /// Returns the animations played by the current state, e.g. all the animations of a
+    /// [`StateKind::Blend`] state. While transitioning, the animations of the previous state
+    /// are not included.
+    pub fn animations(&self) -> impl Iterator<Item = PlayingAnimation<'s>> {
+        let raw_state = self.current_state.raw_state;
+
+        PlayingAnimations::new(RawPlayingAnimations(self.raw_state_machine)).filter(
+            move |animation| unsafe {
+                ffi::rive_rs_layer_state_plays(raw_state, animation.raw_linear_animation)
+            },
+        )
+    }
+}
+
+impl fmt::Debug for Layer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layer")
+            .field("index", &self.index)
+            .field("name", &self.name())
+            .field("current_state", &self.current_state)
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawLayers {
+    pub raw_state_machine: *mut ffi::StateMachine,
+    pub current_states: *const *const ffi::LayerState,
+}
+
+impl Raw for RawLayers {
+    type Item<'s> = Layer<'s>;
+
+    fn len(self) -> usize {
+        unsafe { ffi::rive_rs_state_machine_layer_count(self.raw_state_machine) }
+    }
+
+    unsafe fn get<'s>(self, index: usize) -> Self::Item<'s> {
+        Layer {
+            raw_state_machine: self.raw_state_machine,
+            index,
+            current_state: LayerState::new(*self.current_states.add(index)),
+        }
+    }
+}
+
+impl_iter!(Layers, Layer, RawLayers, 's);
+
+/// A layer that changed its state during the last advance. See
+/// [`StateMachine::state_changes`](crate::StateMachine::state_changes) for the changes that are
+/// not reported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateChange<'s> {
+    pub layer: usize,
+    pub from: LayerState<'s>,
+    pub to: LayerState<'s>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawStateChange {
+    pub layer: usize,
+    pub from: *const ffi::LayerState,
+    pub to: *const ffi::LayerState,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RawStateChanges {
+    pub data: *const RawStateChange,
+    pub len: usize,
+}
+
+impl Raw for RawStateChanges {
+    type Item<'s> = StateChange<'s>;
+
+    fn len(self) -> usize {
+        self.len
+    }
+
+    unsafe fn get<'s>(self, index: usize) -> Self::Item<'s> {
+        let change = *self.data.add(index);
+
+        StateChange {
+            layer: change.layer,
+            from: LayerState::new(change.from),
+            to: LayerState::new(change.to),
+        }
+    }
+}
+
+impl_iter!(StateChanges, StateChange, RawStateChanges, 's);
@@ -1,8 +1,8 @@
-use alloc::sync::Arc;
-use core::{fmt, marker::PhantomData, ptr::NonNull};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{fmt, marker::PhantomData, mem, ptr::NonNull};
 
 use crate::{
-    artboard::{Artboard, ArtboardInner},
+    artboard::{components, Artboard, ArtboardInner},
     ffi,
     instantiate::{Handle, Instantiate},
     renderer::Renderer,
@@ -11,15 +11,28 @@ use crate::{
 
 mod events;
 mod inputs;
+mod layers;
+mod session;
 
+use self::events::Listener;
+use self::layers::{RawLayers, RawPlayingAnimations, RawStateChange, RawStateChanges};
 pub use self::{
-    events::{Event, EventIter, Property},
-    inputs::{Bool, InputIter, Number, Trigger},
+    events::{Event, EventIter, ListenerId, Property},
+    inputs::{Bool, Input, InputIter, InputSnapshot, InputState, InputValue, Number, Trigger},
+    layers::{
+        Layer, LayerState, Layers, PlayingAnimation, PlayingAnimations, StateChange, StateChanges,
+        StateKind,
+    },
+    session::{Action, Entry, Player, Recorder, Session, Step},
 };
 
 pub struct StateMachine<R: Renderer> {
     artboard: Arc<ArtboardInner>,
     raw_state_machine: *mut ffi::StateMachine,
+    current_states: Vec<*const ffi::LayerState>,
+    state_changes: Vec<RawStateChange>,
+    listeners: Vec<Listener>,
+    next_listener_id: u64,
     _phantom: PhantomData<R>,
 }
 
@@ -55,10 +68,25 @@ impl<R: Renderer> Instantiate for StateMachine<R> {
             },
         }
 
-        raw_state_machine.map(|raw_state_machine| StateMachine {
-            artboard: artboard.as_inner().clone(),
-            raw_state_machine: raw_state_machine.as_ptr(),
-            _phantom: PhantomData,
+        raw_state_machine.map(|raw_state_machine| {
+            let raw_state_machine = raw_state_machine.as_ptr();
+            // Every layer starts in its entry state.
+            let current_states =
+                (0..unsafe { ffi::rive_rs_state_machine_layer_count(raw_state_machine) })
+                    .map(|index| unsafe {
+                        ffi::rive_rs_state_machine_layer_entry_state(raw_state_machine, index)
+                    })
+                    .collect();
+
+            StateMachine {
+                artboard: artboard.as_inner().clone(),
+                raw_state_machine,
+                current_states,
+                state_changes: Vec::new(),
+                listeners: Vec::new(),
+                next_listener_id: 0,
+                _phantom: PhantomData,
+            }
         })
     }
 }
@@ -76,16 +104,160 @@ impl<R: Renderer> StateMachine<R> {
         self.raw_state_machine as *mut ffi::Scene
     }
 
+    fn raw_hit_test(&self, x: f32, y: f32) -> bool {
+        unsafe { ffi::rive_rs_state_machine_hit_test(self.raw_state_machine, x, y) }
+    }
+
     #[inline]
     pub fn events(&self) -> EventIter {
         EventIter::new(events::RawStateMachine(self.raw_state_machine))
     }
 
+    fn add_listener(
+        &mut self,
+        name: Option<String>,
+        callback: Box<dyn FnMut(&Event) + Send>,
+    ) -> ListenerId {
+        let id = ListenerId(self.next_listener_id);
+        self.next_listener_id += 1;
+
+        self.listeners.push(Listener { id, name, callback });
+
+        id
+    }
+
+    /// Calls `callback` for every reported event named `name` during
+    /// [`advance_and_apply`](crate::scene::Scene::advance_and_apply), after the state machine
+    /// has been advanced. Listeners are called in the order in which they were added.
+    pub fn on_event(
+        &mut self,
+        name: impl Into<String>,
+        callback: impl FnMut(&Event) + Send + 'static,
+    ) -> ListenerId {
+        self.add_listener(Some(name.into()), Box::new(callback))
+    }
+
+    /// Calls `callback` for every reported event, like [`StateMachine::on_event`].
+    pub fn on_any_event(&mut self, callback: impl FnMut(&Event) + Send + 'static) -> ListenerId {
+        self.add_listener(None, Box::new(callback))
+    }
+
+    /// Removes a listener and returns whether it was still registered.
+    pub fn remove_listener(&mut self, id: ListenerId) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|listener| listener.id != id);
+
+        self.listeners.len() != len
+    }
+
+    fn notify_listeners(&mut self) {
+        if self.listeners.is_empty() {
+            return;
+        }
+
+        for event in EventIter::new(events::RawStateMachine(self.raw_state_machine)) {
+            for listener in &mut self.listeners {
+                if listener.matches(&event) {
+                    (listener.callback)(&event);
+                }
+            }
+        }
+    }
+
+    fn on_advance(&mut self) {
+        self.update_states();
+        self.notify_listeners();
+    }
+
+    /// Iterates over the layers of the state machine together with their current states.
+    #[inline]
+    pub fn layers(&self) -> Layers {
+        Layers::new(RawLayers {
+            raw_state_machine: self.raw_state_machine,
+            current_states: self.current_states.as_ptr(),
+        })
+    }
+
+    /// Iterates over all animations that are being played by any layer, including the ones
+    /// that are being transitioned from.
+    #[inline]
+    pub fn playing_animations(&self) -> PlayingAnimations {
+        PlayingAnimations::new(RawPlayingAnimations(self.raw_state_machine))
+    }
+
+    /// Iterates over the state changes of the last advance, in the order of their layers.
+    ///
+    /// Every layer reports at most one change, from the state it was in before the advance to
+    /// the one it is in after it. States that a layer only passes through during a single
+    /// advance are not reported, and a layer that ends up back in its previous state, e.g. going
+    /// from `A` to `B` and back to `A`, doesn't report a change at all. Advancing in smaller
+    /// steps makes such transitions observable.
+    #[inline]
+    pub fn state_changes(&self) -> StateChanges {
+        StateChanges::new(RawStateChanges {
+            data: self.state_changes.as_ptr(),
+            len: self.state_changes.len(),
+        })
+    }
+
+    fn update_states(&mut self) {
+        self.state_changes.clear();
+
+        for (layer, current_state) in self.current_states.iter_mut().enumerate() {
+            let state = unsafe {
+                ffi::rive_rs_state_machine_layer_current_state(self.raw_state_machine, layer)
+            };
+
+            if !state.is_null() && state != *current_state {
+                self.state_changes.push(RawStateChange {
+                    layer,
+                    from: mem::replace(current_state, state),
+                    to: state,
+                });
+            }
+        }
+    }
+
     #[inline]
     pub fn inputs(&self) -> InputIter {
         InputIter::new(inputs::RawStateMachine(self.raw_state_machine))
     }
 
+    /// Captures the current values of all inputs.
+    pub fn input_snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            inputs: self
+                .inputs()
+                .map(|input| InputState {
+                    name: input.name().into(),
+                    value: input.value(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Sets the inputs of the state machine to the values in `snapshot`. Triggers are not
+    /// fired, since they don't keep a value.
+    ///
+    /// Returns `false` if some input of `snapshot` doesn't exist in this state machine with the
+    /// same name and kind, in which case it is skipped.
+    pub fn restore_inputs(&mut self, snapshot: &InputSnapshot) -> bool {
+        let mut restored_all = true;
+
+        for input in &snapshot.inputs {
+            restored_all &= match input.value {
+                InputValue::Bool(val) => self.get_bool(&input.name).map(|mut bool| bool.set(val)),
+                InputValue::Number(val) => self
+                    .get_number(&input.name)
+                    .map(|mut number| number.set(val)),
+                InputValue::Trigger => self.get_trigger(&input.name).map(|_| ()),
+            }
+            .is_some();
+        }
+
+        restored_all
+    }
+
     #[inline]
     pub fn get_bool(&self, name: &str) -> Option<Bool> {
         unsafe {
@@ -121,11 +293,34 @@ impl<R: Renderer> StateMachine<R> {
             .map(|ptr| Trigger::new(ptr.as_ptr()))
         }
     }
+
+    /// Same as [`StateMachine::get_bool`], but for the state machine of the nested artboard at
+    /// `path`, e.g. `"card/stars"`.
+    #[inline]
+    pub fn get_bool_at_path(&self, name: &str, path: &str) -> Option<Bool> {
+        components::nested_artboard_at_path(self.raw_artboard(), path)?.get_bool(name)
+    }
+
+    /// Same as [`StateMachine::get_number`], but for the state machine of the nested artboard at
+    /// `path`, e.g. `"card/stars"`.
+    #[inline]
+    pub fn get_number_at_path(&self, name: &str, path: &str) -> Option<Number> {
+        components::nested_artboard_at_path(self.raw_artboard(), path)?.get_number(name)
+    }
+
+    /// Same as [`StateMachine::get_trigger`], but for the state machine of the nested artboard at
+    /// `path`, e.g. `"card/stars"`.
+    #[inline]
+    pub fn get_trigger_at_path(&self, name: &str, path: &str) -> Option<Trigger> {
+        components::nested_artboard_at_path(self.raw_artboard(), path)?.get_trigger(name)
+    }
 }
 
 impl<R: Renderer> fmt::Debug for StateMachine<R> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("StateMachine").finish()
+        f.debug_struct("StateMachine")
+            .field("listeners", &self.listeners.len())
+            .finish()
     }
 }
 
@@ -140,4 +335,4 @@ impl<R: Renderer> Drop for StateMachine<R> {
 unsafe impl<R: Renderer> Send for StateMachine<R> {}
 unsafe impl<R: Renderer> Sync for StateMachine<R> {}
 
-impl_scene!(StateMachine);
+impl_scene!(StateMachine, on_advance);
@@ -0,0 +1,480 @@
+use alloc::{string::String, vec::Vec};
+use core::{any::Any, fmt, time::Duration};
+
+use crate::{
+    linear_animation::Loop,
+    renderer::Renderer,
+    scene::{self, HitResult, Scene, Viewport},
+};
+
+use super::{Event, InputSnapshot, StateMachine};
+
+/// An interaction with a [`StateMachine`] recorded by a [`Recorder`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Action {
+    SetBool {
+        name: String,
+        value: bool,
+    },
+    SetNumber {
+        name: String,
+        value: f32,
+    },
+    FireTrigger {
+        name: String,
+    },
+    /// Pointer coordinates are in viewport space, which is why the viewport is recorded too.
+    PointerDown {
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: Viewport,
+    },
+    PointerMove {
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: Viewport,
+    },
+    PointerUp {
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: Viewport,
+    },
+    PointerExit {
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: Viewport,
+    },
+    Advance {
+        elapsed: Duration,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Entry {
+    /// Sum of all the advances recorded before the action.
+    pub timestamp: Duration,
+    pub action: Action,
+}
+
+/// A recorded session that can be replayed with a [`Player`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Session {
+    /// Values of the inputs when the recording started.
+    pub inputs: InputSnapshot,
+    pub entries: Vec<Entry>,
+}
+
+/// Wraps a [`StateMachine`] and records every input change, pointer event and advance into a
+/// [`Session`].
+///
+/// Timestamps are the sum of the recorded advances rather than wall-clock time, so that a
+/// session replays identically no matter how long it took to record.
+pub struct Recorder<R: Renderer> {
+    state_machine: StateMachine<R>,
+    session: Session,
+    time: Duration,
+}
+
+impl<R: Renderer> Recorder<R> {
+    pub fn new(state_machine: StateMachine<R>) -> Self {
+        let session = Session {
+            inputs: state_machine.input_snapshot(),
+            entries: Vec::new(),
+        };
+
+        Self {
+            state_machine,
+            session,
+            time: Duration::ZERO,
+        }
+    }
+
+    #[inline]
+    pub fn state_machine(&self) -> &StateMachine<R> {
+        &self.state_machine
+    }
+
+    /// Returns the wrapped state machine, e.g. to register listeners. Changes made through it
+    /// are not recorded.
+    #[inline]
+    pub fn state_machine_mut(&mut self) -> &mut StateMachine<R> {
+        &mut self.state_machine
+    }
+
+    #[inline]
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    #[inline]
+    pub fn into_parts(self) -> (StateMachine<R>, Session) {
+        (self.state_machine, self.session)
+    }
+
+    fn record(&mut self, action: Action) {
+        self.session.entries.push(Entry {
+            timestamp: self.time,
+            action,
+        });
+    }
+
+    /// Sets the bool input named `name` and returns whether it exists. Only existing inputs are
+    /// recorded.
+    pub fn set_bool(&mut self, name: &str, value: bool) -> bool {
+        let Some(mut bool) = self.state_machine.get_bool(name) else {
+            return false;
+        };
+        bool.set(value);
+
+        self.record(Action::SetBool {
+            name: name.into(),
+            value,
+        });
+
+        true
+    }
+
+    /// Sets the number input named `name` and returns whether it exists. Only existing inputs
+    /// are recorded.
+    pub fn set_number(&mut self, name: &str, value: f32) -> bool {
+        let Some(mut number) = self.state_machine.get_number(name) else {
+            return false;
+        };
+        number.set(value);
+
+        self.record(Action::SetNumber {
+            name: name.into(),
+            value,
+        });
+
+        true
+    }
+
+    /// Fires the trigger input named `name` and returns whether it exists. Only existing inputs
+    /// are recorded.
+    pub fn fire_trigger(&mut self, name: &str) -> bool {
+        let Some(mut trigger) = self.state_machine.get_trigger(name) else {
+            return false;
+        };
+        trigger.fire();
+
+        self.record(Action::FireTrigger { name: name.into() });
+
+        true
+    }
+}
+
+/// Delegates to the wrapped state machine and records pointer events and advances, so that a
+/// recorder can be driven and drawn like any other [`Scene`].
+impl<R: Renderer> Scene<R> for Recorder<R> {
+    #[inline]
+    fn width(&self) -> f32 {
+        self.state_machine.width()
+    }
+
+    #[inline]
+    fn height(&self) -> f32 {
+        self.state_machine.height()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.state_machine.name()
+    }
+
+    #[inline]
+    fn r#loop(&self) -> Loop {
+        self.state_machine.r#loop()
+    }
+
+    #[inline]
+    fn is_translucent(&self) -> bool {
+        self.state_machine.is_translucent()
+    }
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.state_machine.duration()
+    }
+
+    #[inline]
+    fn hit_test(&self, x: f32, y: f32, viewport: &Viewport) -> bool {
+        self.state_machine.hit_test(x, y, viewport)
+    }
+
+    fn pointer_down_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult {
+        let result = self
+            .state_machine
+            .pointer_down_with_id(pointer_id, x, y, viewport);
+        self.record(Action::PointerDown {
+            pointer_id,
+            x,
+            y,
+            viewport: viewport.clone(),
+        });
+
+        result
+    }
+
+    fn pointer_move_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult {
+        let result = self
+            .state_machine
+            .pointer_move_with_id(pointer_id, x, y, viewport);
+        self.record(Action::PointerMove {
+            pointer_id,
+            x,
+            y,
+            viewport: viewport.clone(),
+        });
+
+        result
+    }
+
+    fn pointer_up_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult {
+        let result = self
+            .state_machine
+            .pointer_up_with_id(pointer_id, x, y, viewport);
+        self.record(Action::PointerUp {
+            pointer_id,
+            x,
+            y,
+            viewport: viewport.clone(),
+        });
+
+        result
+    }
+
+    fn pointer_exit_with_id(
+        &mut self,
+        pointer_id: i32,
+        x: f32,
+        y: f32,
+        viewport: &Viewport,
+    ) -> HitResult {
+        let result = self
+            .state_machine
+            .pointer_exit_with_id(pointer_id, x, y, viewport);
+        self.record(Action::PointerExit {
+            pointer_id,
+            x,
+            y,
+            viewport: viewport.clone(),
+        });
+
+        result
+    }
+
+    fn advance_and_apply(&mut self, elapsed: Duration) -> bool {
+        let keep_going = self.state_machine.advance_and_apply(elapsed);
+
+        self.record(Action::Advance { elapsed });
+        self.time += elapsed;
+
+        keep_going
+    }
+
+    #[inline]
+    fn draw(&self, renderer: &mut R) {
+        self.state_machine.draw(renderer);
+    }
+
+    fn advance_and_maybe_draw(
+        &mut self,
+        renderer: &mut R,
+        elapsed: Duration,
+        viewport: &mut Viewport,
+    ) -> bool {
+        scene::resize_to_viewport(self.state_machine.raw_artboard(), viewport);
+
+        if !self.advance_and_apply(elapsed) {
+            return false;
+        }
+
+        let (view_transform, _) =
+            scene::view_transforms(self.state_machine.raw_artboard(), viewport);
+
+        renderer.state_push();
+        renderer.transform(&view_transform);
+
+        self.draw(renderer);
+
+        renderer.state_pop();
+
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl<R: Renderer> fmt::Debug for Recorder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("entries", &self.session.entries.len())
+            .field("time", &self.time)
+            .finish()
+    }
+}
+
+/// The result of replaying the actions of a [`Session`] up to and including an advance.
+#[derive(Clone, Debug)]
+pub struct Step {
+    /// Timestamp of the advance, i.e. the time before it was applied.
+    pub timestamp: Duration,
+    pub elapsed: Duration,
+    /// Events reported by the state machine during the advance.
+    pub events: Vec<Event>,
+}
+
+/// Replays a [`Session`] against a state machine, usually a fresh instance of the one it was
+/// recorded with.
+///
+/// Every call to [`Iterator::next`] applies the recorded actions up to the next advance and
+/// yields the events reported by it, so that they can be compared with the ones seen while
+/// recording. Actions recorded after the last advance are applied when the session ends.
+pub struct Player<R: Renderer> {
+    state_machine: StateMachine<R>,
+    session: Session,
+    index: usize,
+}
+
+impl<R: Renderer> Player<R> {
+    /// Restores the session's initial inputs onto `state_machine` and prepares the replay.
+    pub fn new(mut state_machine: StateMachine<R>, session: Session) -> Self {
+        state_machine.restore_inputs(&session.inputs);
+
+        Self {
+            state_machine,
+            session,
+            index: 0,
+        }
+    }
+
+    #[inline]
+    pub fn state_machine(&self) -> &StateMachine<R> {
+        &self.state_machine
+    }
+
+    #[inline]
+    pub fn state_machine_mut(&mut self) -> &mut StateMachine<R> {
+        &mut self.state_machine
+    }
+
+    #[inline]
+    pub fn into_state_machine(self) -> StateMachine<R> {
+        self.state_machine
+    }
+
+    /// Returns `true` once all recorded actions have been replayed.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.index >= self.session.entries.len()
+    }
+}
+
+impl<R: Renderer> fmt::Debug for Player<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Player")
+            .field("entries", &self.session.entries.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<R: Renderer> Iterator for Player<R> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.session.entries.get(self.index) {
+            self.index += 1;
+
+            let state_machine = &mut self.state_machine;
+            match &entry.action {
+                Action::SetBool { name, value } => {
+                    if let Some(mut bool) = state_machine.get_bool(name) {
+                        bool.set(*value);
+                    }
+                }
+                Action::SetNumber { name, value } => {
+                    if let Some(mut number) = state_machine.get_number(name) {
+                        number.set(*value);
+                    }
+                }
+                Action::FireTrigger { name } => {
+                    if let Some(mut trigger) = state_machine.get_trigger(name) {
+                        trigger.fire();
+                    }
+                }
+                Action::PointerDown {
+                    pointer_id,
+                    x,
+                    y,
+                    viewport,
+                } => {
+                    state_machine.pointer_down_with_id(*pointer_id, *x, *y, viewport);
+                }
+                Action::PointerMove {
+                    pointer_id,
+                    x,
+                    y,
+                    viewport,
+                } => {
+                    state_machine.pointer_move_with_id(*pointer_id, *x, *y, viewport);
+                }
+                Action::PointerUp {
+                    pointer_id,
+                    x,
+                    y,
+                    viewport,
+                } => {
+                    state_machine.pointer_up_with_id(*pointer_id, *x, *y, viewport);
+                }
+                Action::PointerExit {
+                    pointer_id,
+                    x,
+                    y,
+                    viewport,
+                } => {
+                    state_machine.pointer_exit_with_id(*pointer_id, *x, *y, viewport);
+                }
+                Action::Advance { elapsed } => {
+                    state_machine.advance_and_apply(*elapsed);
+
+                    return Some(Step {
+                        timestamp: entry.timestamp,
+                        elapsed: *elapsed,
+                        events: state_machine.events().collect(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
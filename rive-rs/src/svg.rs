@@ -0,0 +1,782 @@
+//! A [`Renderer`](renderer::Renderer) back-end that records a frame as an SVG document, e.g. to
+//! export static vector images of a [`Scene`](crate::scene::Scene).
+
+use std::{
+    fmt::{self, Write},
+    io::Cursor,
+    sync::Arc,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{io::Reader, ImageFormat};
+
+use crate::{renderer, util};
+
+fn mime_type(format: ImageFormat) -> Option<&'static str> {
+    Some(match format {
+        ImageFormat::Png => "image/png",
+        ImageFormat::Jpeg => "image/jpeg",
+        ImageFormat::Gif => "image/gif",
+        ImageFormat::WebP => "image/webp",
+        ImageFormat::Bmp => "image/bmp",
+        ImageFormat::Avif => "image/avif",
+        _ => return None,
+    })
+}
+
+fn mix_blend_mode(blend_mode: renderer::BlendMode) -> Option<&'static str> {
+    Some(match blend_mode {
+        renderer::BlendMode::SrcOver => return None,
+        renderer::BlendMode::Screen => "screen",
+        renderer::BlendMode::Overlay => "overlay",
+        renderer::BlendMode::Darken => "darken",
+        renderer::BlendMode::Lighten => "lighten",
+        renderer::BlendMode::ColorDodge => "color-dodge",
+        renderer::BlendMode::ColorBurn => "color-burn",
+        renderer::BlendMode::HardLight => "hard-light",
+        renderer::BlendMode::SoftLight => "soft-light",
+        renderer::BlendMode::Difference => "difference",
+        renderer::BlendMode::Exclusion => "exclusion",
+        renderer::BlendMode::Multiply => "multiply",
+        renderer::BlendMode::Hue => "hue",
+        renderer::BlendMode::Saturation => "saturation",
+        renderer::BlendMode::Color => "color",
+        renderer::BlendMode::Luminosity => "luminosity",
+    })
+}
+
+fn mul(a: &[f32; 6], b: &[f32; 6]) -> [f32; 6] {
+    [
+        a[0] * b[0] + a[2] * b[1],
+        a[1] * b[0] + a[3] * b[1],
+        a[0] * b[2] + a[2] * b[3],
+        a[1] * b[2] + a[3] * b[3],
+        a[0] * b[4] + a[2] * b[5] + a[4],
+        a[1] * b[4] + a[3] * b[5] + a[5],
+    ]
+}
+
+const IDENTITY: [f32; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+struct Matrix<'t>(&'t [f32; 6]);
+
+impl fmt::Display for Matrix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "matrix({a} {b} {c} {d} {e} {g})")
+    }
+}
+
+struct Rgb(renderer::Color);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0.r, self.0.g, self.0.b)
+    }
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    inner: Vec<u8>,
+}
+
+impl Buffer {
+    #[inline]
+    pub fn as_f32_pairs(&self) -> &[[f32; 2]] {
+        bytemuck::cast_slice(&self.inner)
+    }
+
+    #[inline]
+    pub fn as_u16s(&self) -> &[u16] {
+        bytemuck::cast_slice(&self.inner)
+    }
+}
+
+impl renderer::Buffer for Buffer {
+    #[inline]
+    fn new(
+        _type: renderer::BufferType,
+        _flags: renderer::BufferFlags,
+        len_in_bytes: usize,
+    ) -> Self {
+        Self {
+            inner: vec![0; len_in_bytes],
+        }
+    }
+
+    #[inline]
+    fn map(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn unmap(&mut self) {}
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Command {
+    Move([f32; 2]),
+    Line([f32; 2]),
+    Cubic([f32; 2], [f32; 2], [f32; 2]),
+    Close,
+}
+
+impl Command {
+    fn transform(self, transform: &[f32; 6]) -> Self {
+        let apply = |[x, y]: [f32; 2]| {
+            [
+                transform[0] * x + transform[2] * y + transform[4],
+                transform[1] * x + transform[3] * y + transform[5],
+            ]
+        };
+
+        match self {
+            Self::Move(p) => Self::Move(apply(p)),
+            Self::Line(p) => Self::Line(apply(p)),
+            Self::Cubic(o, i, p) => Self::Cubic(apply(o), apply(i), apply(p)),
+            Self::Close => Self::Close,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Path {
+    commands: Vec<Command>,
+    fill_rule: crate::path::FillRule,
+}
+
+impl Path {
+    fn write_data(&self, svg: &mut String) {
+        for command in &self.commands {
+            let _ = match command {
+                Command::Move([x, y]) => write!(svg, "M{x} {y}"),
+                Command::Line([x, y]) => write!(svg, "L{x} {y}"),
+                Command::Cubic([ox, oy], [ix, iy], [x, y]) => {
+                    write!(svg, "C{ox} {oy} {ix} {iy} {x} {y}")
+                }
+                Command::Close => write!(svg, "Z"),
+            };
+        }
+    }
+
+    fn fill_rule(&self) -> &'static str {
+        match self.fill_rule {
+            crate::path::FillRule::NonZero => "nonzero",
+            crate::path::FillRule::EvenOdd => "evenodd",
+        }
+    }
+}
+
+impl Default for Path {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            fill_rule: crate::path::FillRule::NonZero,
+        }
+    }
+}
+
+impl renderer::Path for Path {
+    fn new(commands: &mut crate::path::Commands, fill_rule: crate::path::FillRule) -> Self {
+        let mut path = Self::default();
+
+        for (verb, points) in commands {
+            match verb {
+                crate::path::Verb::Move => path.move_to(points[0].x, points[0].y),
+                crate::path::Verb::Line => path.line_to(points[0].x, points[0].y),
+                crate::path::Verb::Cubic => path.cubic_to(
+                    points[0].x,
+                    points[0].y,
+                    points[1].x,
+                    points[1].y,
+                    points[2].x,
+                    points[2].y,
+                ),
+                crate::path::Verb::Close => path.close(),
+            }
+        }
+
+        path.set_fill_rule(fill_rule);
+
+        path
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.commands.clear();
+    }
+
+    #[inline]
+    fn extend(&mut self, from: &Self, transform: &[f32; 6]) {
+        self.commands.extend(
+            from.commands
+                .iter()
+                .map(|command| command.transform(transform)),
+        );
+    }
+
+    #[inline]
+    fn set_fill_rule(&mut self, fill_rule: crate::path::FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.commands.push(Command::Move([x, y]));
+    }
+
+    #[inline]
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.commands.push(Command::Line([x, y]));
+    }
+
+    #[inline]
+    fn cubic_to(&mut self, ox: f32, oy: f32, ix: f32, iy: f32, x: f32, y: f32) {
+        self.commands
+            .push(Command::Cubic([ox, oy], [ix, iy], [x, y]));
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.commands.push(Command::Close);
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Brush {
+    Solid(renderer::Color),
+    Gradient(Gradient),
+}
+
+#[derive(Debug)]
+pub struct Paint {
+    style: renderer::PaintStyle,
+    brush: Brush,
+    thickness: f32,
+    join: renderer::StrokeJoin,
+    cap: renderer::StrokeCap,
+    blend_mode: renderer::BlendMode,
+}
+
+impl Default for Paint {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            style: renderer::PaintStyle::Fill,
+            brush: Brush::Solid(renderer::Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            }),
+            thickness: 1.0,
+            join: renderer::StrokeJoin::Miter,
+            cap: renderer::StrokeCap::Butt,
+            blend_mode: renderer::BlendMode::SrcOver,
+        }
+    }
+}
+
+impl renderer::Paint for Paint {
+    type Gradient = Gradient;
+
+    #[inline]
+    fn set_style(&mut self, style: renderer::PaintStyle) {
+        self.style = style;
+    }
+
+    #[inline]
+    fn set_color(&mut self, color: renderer::Color) {
+        self.brush = Brush::Solid(color);
+    }
+
+    #[inline]
+    fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness;
+    }
+
+    #[inline]
+    fn set_join(&mut self, join: renderer::StrokeJoin) {
+        self.join = join;
+    }
+
+    #[inline]
+    fn set_cap(&mut self, cap: renderer::StrokeCap) {
+        self.cap = cap;
+    }
+
+    #[inline]
+    fn set_blend_mode(&mut self, blend_mode: renderer::BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    #[inline]
+    fn set_gradient(&mut self, gradient: &Self::Gradient) {
+        self.brush = Brush::Gradient(gradient.clone());
+    }
+
+    #[inline]
+    fn invalidate_stroke(&mut self) {}
+}
+
+#[derive(Clone, Debug)]
+enum GradientKind {
+    Linear { start: [f32; 2], end: [f32; 2] },
+    Radial { center: [f32; 2], radius: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    kind: GradientKind,
+    stops: Vec<(f32, renderer::Color)>,
+}
+
+impl Gradient {
+    fn new(kind: GradientKind, colors: &[renderer::Color], stops: &[f32]) -> Self {
+        Self {
+            kind,
+            stops: stops.iter().copied().zip(colors.iter().copied()).collect(),
+        }
+    }
+
+    fn write_def(&self, svg: &mut String, id: usize) {
+        let _ = match self.kind {
+            GradientKind::Linear {
+                start: [x1, y1],
+                end: [x2, y2],
+            } => write!(
+                svg,
+                r#"<linearGradient id="gradient{id}" gradientUnits="userSpaceOnUse" x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}">"#,
+            ),
+            GradientKind::Radial {
+                center: [cx, cy],
+                radius,
+            } => write!(
+                svg,
+                r#"<radialGradient id="gradient{id}" gradientUnits="userSpaceOnUse" cx="{cx}" cy="{cy}" r="{radius}">"#,
+            ),
+        };
+
+        for &(offset, color) in &self.stops {
+            let _ = write!(
+                svg,
+                r#"<stop offset="{offset}" stop-color="{}" stop-opacity="{}"/>"#,
+                Rgb(color),
+                color.a as f32 / 255.0,
+            );
+        }
+
+        svg.push_str(match self.kind {
+            GradientKind::Linear { .. } => "</linearGradient>",
+            GradientKind::Radial { .. } => "</radialGradient>",
+        });
+    }
+}
+
+impl renderer::Gradient for Gradient {
+    #[inline]
+    fn new_linear(
+        sx: f32,
+        sy: f32,
+        ex: f32,
+        ey: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(
+            GradientKind::Linear {
+                start: [sx, sy],
+                end: [ex, ey],
+            },
+            colors,
+            stops,
+        )
+    }
+
+    #[inline]
+    fn new_radial(
+        cx: f32,
+        cy: f32,
+        radius: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(
+            GradientKind::Radial {
+                center: [cx, cy],
+                radius,
+            },
+            colors,
+            stops,
+        )
+    }
+}
+
+#[derive(Debug)]
+struct ImageData {
+    data_uri: String,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug)]
+pub struct Image {
+    inner: Arc<ImageData>,
+}
+
+impl renderer::Image for Image {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let reader = Reader::new(Cursor::new(data)).with_guessed_format().ok()?;
+        let mime_type = mime_type(reader.format()?)?;
+        let (width, height) = reader.into_dimensions().ok()?;
+
+        Some(Image {
+            inner: Arc::new(ImageData {
+                data_uri: format!("data:{mime_type};base64,{}", STANDARD.encode(data)),
+                width,
+                height,
+            }),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    transform: [f32; 6],
+    /// Number of `<g clip-path>` groups opened at this level.
+    clips: usize,
+}
+
+impl Default for State {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            transform: IDENTITY,
+            clips: 0,
+        }
+    }
+}
+
+/// Records draw calls as the elements of a `width`x`height` SVG document.
+///
+/// Clips are emitted as nested `<g clip-path>` groups such that they intersect with all the clips
+/// set before them, at the same level or at enclosing ones. Image meshes are drawn triangle by triangle, so some SVG viewers show faint
+/// seams between their triangles due to anti-aliasing.
+pub struct Renderer {
+    width: u32,
+    height: u32,
+    defs: String,
+    body: String,
+    images: Vec<Arc<ImageData>>,
+    states: Vec<State>,
+    next_id: usize,
+}
+
+impl Renderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            defs: String::new(),
+            body: String::new(),
+            images: Vec::new(),
+            states: vec![State::default()],
+            next_id: 0,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Discards everything drawn so far, e.g. before drawing a new frame.
+    pub fn clear(&mut self) {
+        self.defs.clear();
+        self.body.clear();
+        self.images.clear();
+        self.states = vec![State::default()];
+        self.next_id = 0;
+    }
+
+    /// Returns the SVG document of everything drawn so far.
+    pub fn to_svg(&self) -> String {
+        let open_clips: usize = self.states.iter().map(|state| state.clips).sum();
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            self.width, self.height,
+        );
+
+        svg.push_str("<defs>");
+        svg.push_str(&self.defs);
+        svg.push_str("</defs>");
+        svg.push_str(&self.body);
+        for _ in 0..open_clips {
+            svg.push_str("</g>");
+        }
+        svg.push_str("</svg>");
+
+        svg
+    }
+
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn last_state(&mut self) -> &mut State {
+        self.states.last_mut().unwrap()
+    }
+
+    /// Returns the id of `image`, adding it to the `<defs>` the first time it's drawn.
+    fn image_id(&mut self, image: &Arc<ImageData>) -> usize {
+        if let Some(id) = self.images.iter().position(|i| Arc::ptr_eq(i, image)) {
+            return id;
+        }
+
+        let id = self.images.len();
+        let _ = write!(
+            self.defs,
+            r#"<image id="image{id}" width="{}" height="{}" xlink:href="{}"/>"#,
+            image.width, image.height, image.data_uri,
+        );
+        self.images.push(image.clone());
+
+        id
+    }
+
+    fn write_blend(&mut self, blend_mode: renderer::BlendMode, opacity: f32) {
+        if let Some(mix_blend_mode) = mix_blend_mode(blend_mode) {
+            let _ = write!(self.body, r#" style="mix-blend-mode:{mix_blend_mode}""#);
+        }
+
+        if opacity != 1.0 {
+            let _ = write!(self.body, r#" opacity="{opacity}""#);
+        }
+    }
+
+    // SVG images cannot be wrapped, so only the filter is honored.
+    fn write_sampler(&mut self, sampler: renderer::ImageSampler) {
+        if sampler.filter == renderer::ImageFilter::Nearest {
+            self.body.push_str(r#" image-rendering="pixelated""#);
+        }
+    }
+}
+
+impl renderer::Renderer for Renderer {
+    type Buffer = Buffer;
+
+    type Path = Path;
+
+    type Paint = Paint;
+
+    type Gradient = Gradient;
+
+    type Image = Image;
+
+    #[inline]
+    fn state_push(&mut self) {
+        let transform = self.last_state().transform;
+
+        self.states.push(State {
+            transform,
+            clips: 0,
+        });
+    }
+
+    #[inline]
+    fn state_pop(&mut self) {
+        if let Some(state) = self.states.pop() {
+            for _ in 0..state.clips {
+                self.body.push_str("</g>");
+            }
+        }
+
+        if self.states.is_empty() {
+            self.states.push(State::default());
+        }
+    }
+
+    #[inline]
+    fn transform(&mut self, transform: &[f32; 6]) {
+        let last_state = self.last_state();
+        last_state.transform = mul(&last_state.transform, transform);
+    }
+
+    fn set_clip(&mut self, path: &Self::Path) {
+        let id = self.next_id();
+        let state = *self.last_state();
+
+        let _ = write!(
+            self.defs,
+            r#"<clipPath id="clip{id}"><path transform="{}" clip-rule="{}" d=""#,
+            Matrix(&state.transform),
+            path.fill_rule(),
+        );
+        path.write_data(&mut self.defs);
+        self.defs.push_str(r#""/></clipPath>"#);
+
+        // Every clip opens its own group nested into the ones opened before it, so that it
+        // intersects with all of their clips.
+        let _ = write!(self.body, r#"<g clip-path="url(#clip{id})">"#);
+
+        self.last_state().clips += 1;
+    }
+
+    fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint) {
+        let transform = self.last_state().transform;
+
+        let brush = match &paint.brush {
+            Brush::Solid(color) => (Rgb(*color).to_string(), color.a as f32 / 255.0),
+            Brush::Gradient(gradient) => {
+                let id = self.next_id();
+                gradient.write_def(&mut self.defs, id);
+                (format!("url(#gradient{id})"), 1.0)
+            }
+        };
+
+        let _ = write!(self.body, r#"<path transform="{}""#, Matrix(&transform));
+
+        let _ = match paint.style {
+            renderer::PaintStyle::Fill => write!(
+                self.body,
+                r#" fill="{}" fill-opacity="{}" fill-rule="{}""#,
+                brush.0,
+                brush.1,
+                path.fill_rule(),
+            ),
+            renderer::PaintStyle::Stroke => write!(
+                self.body,
+                r#" fill="none" stroke="{}" stroke-opacity="{}" stroke-width="{}" stroke-linejoin="{}" stroke-linecap="{}""#,
+                brush.0,
+                brush.1,
+                paint.thickness,
+                match paint.join {
+                    renderer::StrokeJoin::Miter => "miter",
+                    renderer::StrokeJoin::Round => "round",
+                    renderer::StrokeJoin::Bevel => "bevel",
+                },
+                match paint.cap {
+                    renderer::StrokeCap::Butt => "butt",
+                    renderer::StrokeCap::Round => "round",
+                    renderer::StrokeCap::Square => "square",
+                },
+            ),
+        };
+
+        self.write_blend(paint.blend_mode, 1.0);
+
+        self.body.push_str(r#" d=""#);
+        path.write_data(&mut self.body);
+        self.body.push_str(r#""/>"#);
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let image = &image.inner;
+        let id = self.image_id(image);
+
+        let transform = mul(
+            &self.last_state().transform,
+            &[
+                1.0,
+                0.0,
+                0.0,
+                1.0,
+                image.width as f32 * -0.5,
+                image.height as f32 * -0.5,
+            ],
+        );
+
+        let _ = write!(
+            self.body,
+            r##"<use xlink:href="#image{id}" transform="{}""##,
+            Matrix(&transform),
+        );
+        self.write_sampler(sampler);
+        self.write_blend(blend_mode, opacity);
+        self.body.push_str("/>");
+    }
+
+    fn draw_image_mesh(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        vertices: &Self::Buffer,
+        uvs: &Self::Buffer,
+        indices: &Self::Buffer,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let image = &image.inner;
+        let image_id = self.image_id(image);
+        let vertices = vertices.as_f32_pairs();
+        let uvs = uvs.as_f32_pairs();
+
+        let transform = self.last_state().transform;
+
+        // The whole mesh is blended at once, so that its triangles don't blend with each other.
+        self.body.push_str("<g");
+        self.write_sampler(sampler);
+        self.write_blend(blend_mode, opacity);
+        self.body.push('>');
+
+        for triangle_indices in indices.as_u16s().chunks_exact(3) {
+            let points = [
+                vertices[triangle_indices[0] as usize],
+                vertices[triangle_indices[1] as usize],
+                vertices[triangle_indices[2] as usize],
+            ];
+            let uvs = [
+                uvs[triangle_indices[0] as usize],
+                uvs[triangle_indices[1] as usize],
+                uvs[triangle_indices[2] as usize],
+            ];
+
+            let id = self.next_id();
+            let [[ax, ay], [bx, by], [cx, cy]] = points;
+
+            let _ = write!(
+                self.defs,
+                r#"<clipPath id="clip{id}"><path transform="{}" d="M{ax} {ay}L{bx} {by}L{cx} {cy}Z"/></clipPath>"#,
+                Matrix(&transform),
+            );
+
+            let image_transform = mul(
+                &transform,
+                &util::map_uvs_to_triangle(&points, &uvs, image.width, image.height),
+            );
+
+            let _ = write!(
+                self.body,
+                r##"<g clip-path="url(#clip{id})"><use xlink:href="#image{image_id}" transform="{}"/></g>"##,
+                Matrix(&image_transform),
+            );
+        }
+
+        self.body.push_str("</g>");
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("states", &self.states)
+            .finish()
+    }
+}
@@ -0,0 +1,1443 @@
+//! A [`Renderer`](renderer::Renderer) back-end that tessellates frames into triangle lists with
+//! [lyon], for engines that draw with their own GPU pipelines instead of a Rive renderer.
+//!
+//! A [`Frame`] holds a single vertex and index buffer. Its [`Batch`]es are consecutive ranges
+//! of indices that share a [`Material`], a blend mode and a clip, so that each one can be drawn
+//! with a single draw call. Positions are already transformed into the renderer's output space.
+//!
+//! ```
+//! # use rive_rs::{
+//! #     renderer::{BlendMode, Color, Paint as _, Path as _, Renderer as _},
+//! #     tessellation::{Material, Paint, Path, Renderer},
+//! # };
+//! let mut renderer = Renderer::default();
+//!
+//! let mut square = Path::default();
+//! square.move_to(0.0, 0.0);
+//! square.line_to(10.0, 0.0);
+//! square.line_to(10.0, 10.0);
+//! square.line_to(0.0, 10.0);
+//! square.close();
+//!
+//! let mut paint = Paint::default();
+//! paint.set_color(Color { r: 255, g: 0, b: 0, a: 255 });
+//!
+//! renderer.transform(&[2.0, 0.0, 0.0, 2.0, 5.0, 5.0]);
+//! renderer.draw_path(&square, &paint);
+//!
+//! let frame = renderer.frame();
+//! let batch = &frame.batches()[0];
+//!
+//! assert_eq!(batch.material, Material::Solid);
+//! assert_eq!(batch.clip, None);
+//! assert_eq!(frame.indices()[batch.indices.clone()].len(), 6);
+//! assert!(frame.vertices().iter().all(|vertex| vertex.color == [255, 0, 0, 255]));
+//! assert!(frame
+//!     .vertices()
+//!     .iter()
+//!     .any(|vertex| vertex.position == [25.0, 25.0]));
+//!
+//! // Clips and blend modes start new batches.
+//! renderer.state_push();
+//! renderer.set_clip(&square);
+//! renderer.draw_path(&square, &paint);
+//! paint.set_blend_mode(BlendMode::Multiply);
+//! renderer.draw_path(&square, &paint);
+//! renderer.state_pop();
+//!
+//! let frame = renderer.frame();
+//!
+//! assert_eq!(frame.batches().len(), 3);
+//! assert_eq!(frame.clips().len(), 1);
+//! assert_eq!(frame.batches()[1].clip, Some(0));
+//! assert_eq!(frame.batches()[2].blend_mode, BlendMode::Multiply);
+//! ```
+//!
+//! [lyon]: https://github.com/nical/lyon
+
+use std::{
+    fmt,
+    io::Cursor,
+    mem,
+    ops::Range,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+};
+
+use image::io::Reader;
+use lyon::{
+    math::point,
+    path::{FillRule, LineCap, LineJoin},
+    tessellation::{
+        BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator,
+        StrokeVertex, TessellationResult, VertexBuffers,
+    },
+};
+
+use crate::renderer;
+
+static NEXT_TEXTURE_ID: AtomicU64 = AtomicU64::new(0);
+
+fn to_rgba(color: renderer::Color) -> [u8; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+fn apply(transform: &[f32; 6], [x, y]: [f32; 2]) -> [f32; 2] {
+    [
+        transform[0] * x + transform[2] * y + transform[4],
+        transform[1] * x + transform[3] * y + transform[5],
+    ]
+}
+
+fn mul(a: &[f32; 6], b: &[f32; 6]) -> [f32; 6] {
+    [
+        a[0] * b[0] + a[2] * b[1],
+        a[1] * b[0] + a[3] * b[1],
+        a[0] * b[2] + a[2] * b[3],
+        a[1] * b[2] + a[3] * b[3],
+        a[0] * b[4] + a[2] * b[5] + a[4],
+        a[1] * b[4] + a[3] * b[5] + a[5],
+    ]
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    /// Texture coordinates for [`Material::Texture`], gradient coordinates for gradients and
+    /// zero otherwise.
+    pub uv: [f32; 2],
+    /// Straight-alpha RGBA color that multiplies the material's color.
+    pub color: [u8; 4],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    /// Straight-alpha RGBA color.
+    pub color: [u8; 4],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Material {
+    /// Vertex colors only.
+    Solid,
+    /// The color at a vertex is the stops' color at `uv[0]`, clamped to `0.0..=1.0`.
+    LinearGradient(Arc<[GradientStop]>),
+    /// The color at a point is the stops' color at the length of the interpolated `uv`, clamped
+    /// to `0.0..=1.0`. Engines have to compute the length per fragment.
+    RadialGradient(Arc<[GradientStop]>),
+    /// `uv` are normalized coordinates in the texture with `id`.
+    Texture {
+        id: TextureId,
+        sampler: renderer::ImageSampler,
+    },
+}
+
+/// A range of [`Frame::indices`] that can be drawn with a single draw call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    pub material: Material,
+    pub blend_mode: renderer::BlendMode,
+    /// Index of the innermost clip in [`Frame::clips`], if drawing is clipped.
+    pub clip: Option<usize>,
+    pub indices: Range<usize>,
+}
+
+/// Triangles of a clip path, e.g. to be drawn into a stencil buffer. The triangles cover the
+/// clip path with its fill rule already applied. A clip without any triangles, e.g. because its
+/// path could not be tessellated, hides everything drawn with it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clip {
+    /// Index of the clip in [`Frame::clips`] that this one is intersected with, if any.
+    pub parent: Option<usize>,
+    pub indices: Range<usize>,
+}
+
+/// Tessellated geometry of a frame recorded by a [`Renderer`].
+#[derive(Clone, Debug, Default)]
+pub struct Frame {
+    geometry: VertexBuffers<Vertex, u32>,
+    batches: Vec<Batch>,
+    clips: Vec<Clip>,
+    images: Vec<Image>,
+}
+
+impl Frame {
+    #[inline]
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.geometry.vertices
+    }
+
+    /// Triangle list indices into [`Frame::vertices`].
+    #[inline]
+    pub fn indices(&self) -> &[u32] {
+        &self.geometry.indices
+    }
+
+    #[inline]
+    pub fn batches(&self) -> &[Batch] {
+        &self.batches
+    }
+
+    #[inline]
+    pub fn clips(&self) -> &[Clip] {
+        &self.clips
+    }
+
+    /// Images drawn by [`Material::Texture`] batches, each stored once.
+    #[inline]
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.geometry.vertices.clear();
+        self.geometry.indices.clear();
+        self.batches.clear();
+        self.clips.clear();
+        self.images.clear();
+    }
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    inner: Vec<u8>,
+}
+
+impl Buffer {
+    #[inline]
+    pub fn as_f32_pairs(&self) -> &[[f32; 2]] {
+        bytemuck::cast_slice(&self.inner)
+    }
+
+    #[inline]
+    pub fn as_u16s(&self) -> &[u16] {
+        bytemuck::cast_slice(&self.inner)
+    }
+}
+
+impl renderer::Buffer for Buffer {
+    #[inline]
+    fn new(
+        _type: renderer::BufferType,
+        _flags: renderer::BufferFlags,
+        len_in_bytes: usize,
+    ) -> Self {
+        Self {
+            inner: vec![0; len_in_bytes],
+        }
+    }
+
+    #[inline]
+    fn map(&mut self) -> &mut [u8] {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn unmap(&mut self) {}
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    Move([f32; 2]),
+    Line([f32; 2]),
+    Cubic([f32; 2], [f32; 2], [f32; 2]),
+    Close,
+}
+
+#[derive(Debug)]
+pub struct Path {
+    segments: Vec<Segment>,
+    fill_rule: FillRule,
+    // `lyon::path::Path` is immutable, so it's only built lazily the first time it's drawn.
+    path: OnceLock<lyon::path::Path>,
+}
+
+impl Default for Path {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            segments: Vec::new(),
+            fill_rule: FillRule::NonZero,
+            path: OnceLock::new(),
+        }
+    }
+}
+
+impl Path {
+    fn path(&self) -> &lyon::path::Path {
+        self.path.get_or_init(|| {
+            let mut builder = lyon::path::Path::svg_builder();
+
+            for segment in &self.segments {
+                match *segment {
+                    Segment::Move([x, y]) => {
+                        builder.move_to(point(x, y));
+                    }
+                    Segment::Line([x, y]) => {
+                        builder.line_to(point(x, y));
+                    }
+                    Segment::Cubic([ox, oy], [ix, iy], [x, y]) => {
+                        builder.cubic_bezier_to(point(ox, oy), point(ix, iy), point(x, y));
+                    }
+                    Segment::Close => builder.close(),
+                }
+            }
+
+            builder.build()
+        })
+    }
+
+    #[inline]
+    fn push(&mut self, segment: Segment) {
+        self.segments.push(segment);
+        self.path.take();
+    }
+}
+
+impl renderer::Path for Path {
+    fn new(commands: &mut crate::path::Commands, fill_rule: crate::path::FillRule) -> Self {
+        let mut path = Self::default();
+
+        for (verb, points) in commands {
+            match verb {
+                crate::path::Verb::Move => path.move_to(points[0].x, points[0].y),
+                crate::path::Verb::Line => path.line_to(points[0].x, points[0].y),
+                crate::path::Verb::Cubic => path.cubic_to(
+                    points[0].x,
+                    points[0].y,
+                    points[1].x,
+                    points[1].y,
+                    points[2].x,
+                    points[2].y,
+                ),
+                crate::path::Verb::Close => path.close(),
+            }
+        }
+
+        path.set_fill_rule(fill_rule);
+
+        path
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.segments.clear();
+        self.path.take();
+    }
+
+    fn extend(&mut self, from: &Self, transform: &[f32; 6]) {
+        self.segments
+            .extend(from.segments.iter().map(|segment| match *segment {
+                Segment::Move(p) => Segment::Move(apply(transform, p)),
+                Segment::Line(p) => Segment::Line(apply(transform, p)),
+                Segment::Cubic(o, i, p) => Segment::Cubic(
+                    apply(transform, o),
+                    apply(transform, i),
+                    apply(transform, p),
+                ),
+                Segment::Close => Segment::Close,
+            }));
+        self.path.take();
+    }
+
+    #[inline]
+    fn set_fill_rule(&mut self, fill_rule: crate::path::FillRule) {
+        self.fill_rule = match fill_rule {
+            crate::path::FillRule::NonZero => FillRule::NonZero,
+            crate::path::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+    }
+
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.push(Segment::Move([x, y]));
+    }
+
+    #[inline]
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(Segment::Line([x, y]));
+    }
+
+    #[inline]
+    fn cubic_to(&mut self, ox: f32, oy: f32, ix: f32, iy: f32, x: f32, y: f32) {
+        self.push(Segment::Cubic([ox, oy], [ix, iy], [x, y]));
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.push(Segment::Close);
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Shader {
+    Solid([u8; 4]),
+    Gradient(Gradient),
+}
+
+#[derive(Debug)]
+pub struct Paint {
+    style: renderer::PaintStyle,
+    shader: Shader,
+    thickness: f32,
+    join: LineJoin,
+    cap: LineCap,
+    blend_mode: renderer::BlendMode,
+}
+
+impl Default for Paint {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            style: renderer::PaintStyle::Fill,
+            shader: Shader::Solid([0; 4]),
+            thickness: 1.0,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            blend_mode: renderer::BlendMode::SrcOver,
+        }
+    }
+}
+
+impl renderer::Paint for Paint {
+    type Gradient = Gradient;
+
+    #[inline]
+    fn set_style(&mut self, style: renderer::PaintStyle) {
+        self.style = style;
+    }
+
+    #[inline]
+    fn set_color(&mut self, color: renderer::Color) {
+        self.shader = Shader::Solid(to_rgba(color));
+    }
+
+    #[inline]
+    fn set_thickness(&mut self, thickness: f32) {
+        self.thickness = thickness;
+    }
+
+    #[inline]
+    fn set_join(&mut self, join: renderer::StrokeJoin) {
+        self.join = match join {
+            renderer::StrokeJoin::Miter => LineJoin::Miter,
+            renderer::StrokeJoin::Round => LineJoin::Round,
+            renderer::StrokeJoin::Bevel => LineJoin::Bevel,
+        };
+    }
+
+    #[inline]
+    fn set_cap(&mut self, cap: renderer::StrokeCap) {
+        self.cap = match cap {
+            renderer::StrokeCap::Butt => LineCap::Butt,
+            renderer::StrokeCap::Round => LineCap::Round,
+            renderer::StrokeCap::Square => LineCap::Square,
+        };
+    }
+
+    #[inline]
+    fn set_blend_mode(&mut self, blend_mode: renderer::BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    #[inline]
+    fn set_gradient(&mut self, gradient: &Self::Gradient) {
+        self.shader = Shader::Gradient(gradient.clone());
+    }
+
+    #[inline]
+    fn invalidate_stroke(&mut self) {}
+}
+
+#[derive(Clone, Copy, Debug)]
+enum GradientKind {
+    Linear { start: [f32; 2], end: [f32; 2] },
+    Radial { center: [f32; 2], radius: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    kind: GradientKind,
+    stops: Arc<[GradientStop]>,
+}
+
+impl Gradient {
+    fn new(kind: GradientKind, colors: &[renderer::Color], stops: &[f32]) -> Self {
+        let stops = colors
+            .iter()
+            .zip(stops.iter())
+            .map(|(&color, &offset)| GradientStop {
+                offset,
+                color: to_rgba(color),
+            })
+            .collect();
+
+        Self { kind, stops }
+    }
+
+    /// Gradient coordinates of `position`, which is in the same space as the gradient.
+    fn uv(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let [dx, dy] = [end[0] - start[0], end[1] - start[1]];
+                let len_squared = dx * dx + dy * dy;
+
+                if len_squared == 0.0 {
+                    return [0.0; 2];
+                }
+
+                [
+                    ((x - start[0]) * dx + (y - start[1]) * dy) / len_squared,
+                    0.0,
+                ]
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius == 0.0 {
+                    return [0.0; 2];
+                }
+
+                [(x - center[0]) / radius, (y - center[1]) / radius]
+            }
+        }
+    }
+
+    fn material(&self) -> Material {
+        match self.kind {
+            GradientKind::Linear { .. } => Material::LinearGradient(self.stops.clone()),
+            GradientKind::Radial { .. } => Material::RadialGradient(self.stops.clone()),
+        }
+    }
+}
+
+impl renderer::Gradient for Gradient {
+    #[inline]
+    fn new_linear(
+        sx: f32,
+        sy: f32,
+        ex: f32,
+        ey: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(
+            GradientKind::Linear {
+                start: [sx, sy],
+                end: [ex, ey],
+            },
+            colors,
+            stops,
+        )
+    }
+
+    #[inline]
+    fn new_radial(
+        cx: f32,
+        cy: f32,
+        radius: f32,
+        colors: &[renderer::Color],
+        stops: &[f32],
+    ) -> Self {
+        Self::new(
+            GradientKind::Radial {
+                center: [cx, cy],
+                radius,
+            },
+            colors,
+            stops,
+        )
+    }
+}
+
+/// Identifies an [`Image`] for as long as the process runs, e.g. to cache uploaded textures.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TextureId(u64);
+
+/// A decoded image with a unique [`TextureId`]. Clones share their pixels and id.
+#[derive(Clone)]
+pub struct Image {
+    id: TextureId,
+    width: u32,
+    height: u32,
+    pixels: Arc<[u8]>,
+}
+
+impl Image {
+    #[inline]
+    pub fn id(&self) -> TextureId {
+        self.id
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Row-major RGBA pixels with straight (non-premultiplied) alpha.
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+}
+
+impl fmt::Debug for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("id", &self.id)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl renderer::Image for Image {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let image = Reader::new(Cursor::new(data))
+            .with_guessed_format()
+            .ok()?
+            .decode()
+            .ok()?
+            .into_rgba8();
+
+        Some(Image {
+            id: TextureId(NEXT_TEXTURE_ID.fetch_add(1, Ordering::Relaxed)),
+            width: image.width(),
+            height: image.height(),
+            pixels: image.into_raw().into(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State {
+    transform: [f32; 6],
+    clip: Option<usize>,
+}
+
+impl Default for State {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            clip: None,
+        }
+    }
+}
+
+/// Tessellates draw calls into a [`Frame`].
+///
+/// The renderer is meant to be reused across frames: [`Renderer::reset`] clears the frame but
+/// keeps the allocated buffers.
+pub struct Renderer {
+    frame: Frame,
+    states: Vec<State>,
+    tolerance: f32,
+    fill_tessellator: FillTessellator,
+    stroke_tessellator: StrokeTessellator,
+}
+
+impl Renderer {
+    /// Creates a renderer that approximates curves within `tolerance` units of the output
+    /// space.
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            frame: Frame::default(),
+            states: vec![State::default()],
+            tolerance,
+            fill_tessellator: FillTessellator::new(),
+            stroke_tessellator: StrokeTessellator::new(),
+        }
+    }
+
+    #[inline]
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Returns the recorded frame and resets the renderer.
+    pub fn take_frame(&mut self) -> Frame {
+        self.reset_states();
+        mem::take(&mut self.frame)
+    }
+
+    /// Clears the recorded frame and resets the transform and clips, keeping the allocated
+    /// buffers for the next frame.
+    pub fn reset(&mut self) {
+        self.reset_states();
+        self.frame.clear();
+    }
+
+    fn reset_states(&mut self) {
+        self.states.clear();
+        self.states.push(State::default());
+    }
+
+    fn last_state(&mut self) -> &mut State {
+        self.states.last_mut().unwrap()
+    }
+
+    /// Returns the tolerance in the local space of the current transform, or `None` if the
+    /// transform collapses everything onto a line.
+    fn local_tolerance(&mut self) -> Option<f32> {
+        let [a, b, c, d, ..] = self.last_state().transform;
+        let det = a * d - b * c;
+
+        (det != 0.0).then(|| self.tolerance / det.abs().sqrt())
+    }
+
+    /// Tessellates `path` with its fill rule, returning the range of the added indices.
+    fn fill(&mut self, path: &Path, vertex: impl Fn([f32; 2]) -> Vertex) -> Option<Range<usize>> {
+        let tolerance = self.local_tolerance()?;
+        let start = self.geometry_len();
+
+        let result = self.fill_tessellator.tessellate_path(
+            path.path(),
+            &FillOptions::tolerance(tolerance).with_fill_rule(path.fill_rule),
+            &mut BuffersBuilder::new(&mut self.frame.geometry, |v: FillVertex| {
+                vertex(v.position().to_array())
+            }),
+        );
+
+        self.added_indices(start, result)
+    }
+
+    fn stroke(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        vertex: impl Fn([f32; 2]) -> Vertex,
+    ) -> Option<Range<usize>> {
+        let tolerance = self.local_tolerance()?;
+        let start = self.geometry_len();
+
+        let result = self.stroke_tessellator.tessellate_path(
+            path.path(),
+            &StrokeOptions::tolerance(tolerance)
+                .with_line_width(paint.thickness)
+                .with_line_join(paint.join)
+                .with_line_cap(paint.cap),
+            &mut BuffersBuilder::new(&mut self.frame.geometry, |v: StrokeVertex| {
+                vertex(v.position().to_array())
+            }),
+        );
+
+        self.added_indices(start, result)
+    }
+
+    /// Returns the lengths of the vertex and index buffers.
+    fn geometry_len(&self) -> (usize, usize) {
+        (
+            self.frame.geometry.vertices.len(),
+            self.frame.geometry.indices.len(),
+        )
+    }
+
+    /// Returns the range of the indices added since `start`, or drops the partial geometry of a
+    /// failed tessellation.
+    fn added_indices(
+        &mut self,
+        (vertices, indices): (usize, usize),
+        result: TessellationResult,
+    ) -> Option<Range<usize>> {
+        let geometry = &mut self.frame.geometry;
+
+        if result.is_err() {
+            geometry.vertices.truncate(vertices);
+            geometry.indices.truncate(indices);
+
+            return None;
+        }
+
+        Some(indices..geometry.indices.len())
+    }
+
+    /// Adds `indices` to the last batch if it can be drawn in the same draw call, or starts a
+    /// new batch otherwise.
+    fn push_batch(
+        &mut self,
+        material: Material,
+        blend_mode: renderer::BlendMode,
+        indices: Range<usize>,
+    ) {
+        if indices.is_empty() {
+            return;
+        }
+
+        let clip = self.last_state().clip;
+
+        if let Some(batch) = self.frame.batches.last_mut() {
+            if batch.material == material
+                && batch.blend_mode == blend_mode
+                && batch.clip == clip
+                && batch.indices.end == indices.start
+            {
+                batch.indices.end = indices.end;
+                return;
+            }
+        }
+
+        self.frame.batches.push(Batch {
+            material,
+            blend_mode,
+            clip,
+            indices,
+        });
+    }
+
+    fn push_image(&mut self, image: &Image, sampler: renderer::ImageSampler) -> Material {
+        if !self.frame.images.iter().any(|other| other.id == image.id) {
+            self.frame.images.push(image.clone());
+        }
+
+        Material::Texture {
+            id: image.id,
+            sampler,
+        }
+    }
+}
+
+impl Default for Renderer {
+    #[inline]
+    fn default() -> Self {
+        Self::new(FillOptions::DEFAULT_TOLERANCE)
+    }
+}
+
+impl renderer::Renderer for Renderer {
+    type Buffer = Buffer;
+
+    type Path = Path;
+
+    type Paint = Paint;
+
+    type Gradient = Gradient;
+
+    type Image = Image;
+
+    #[inline]
+    fn state_push(&mut self) {
+        let state = *self.last_state();
+        self.states.push(state);
+    }
+
+    #[inline]
+    fn state_pop(&mut self) {
+        self.states.pop();
+
+        if self.states.is_empty() {
+            self.states.push(State::default());
+        }
+    }
+
+    #[inline]
+    fn transform(&mut self, transform: &[f32; 6]) {
+        let state = self.last_state();
+        state.transform = mul(&state.transform, transform);
+    }
+
+    fn set_clip(&mut self, path: &Self::Path) {
+        let transform = self.last_state().transform;
+
+        // A clip that cannot be tessellated still needs to be recorded, clipping everything
+        // away, rather than leaving the following draws unclipped.
+        let indices = self
+            .fill(path, |position| Vertex {
+                position: apply(&transform, position),
+                uv: [0.0; 2],
+                color: [u8::MAX; 4],
+            })
+            .unwrap_or_else(|| {
+                let end = self.frame.geometry.indices.len();
+                end..end
+            });
+
+        let parent = self.last_state().clip;
+        self.frame.clips.push(Clip { parent, indices });
+        self.last_state().clip = Some(self.frame.clips.len() - 1);
+    }
+
+    fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint) {
+        let transform = self.last_state().transform;
+
+        let vertex = |position| match &paint.shader {
+            Shader::Solid(color) => Vertex {
+                position: apply(&transform, position),
+                uv: [0.0; 2],
+                color: *color,
+            },
+            Shader::Gradient(gradient) => Vertex {
+                position: apply(&transform, position),
+                uv: gradient.uv(position),
+                color: [u8::MAX; 4],
+            },
+        };
+
+        let indices = match paint.style {
+            renderer::PaintStyle::Fill => self.fill(path, vertex),
+            renderer::PaintStyle::Stroke => self.stroke(path, paint, vertex),
+        };
+
+        let material = match &paint.shader {
+            Shader::Solid(_) => Material::Solid,
+            Shader::Gradient(gradient) => gradient.material(),
+        };
+
+        if let Some(indices) = indices {
+            self.push_batch(material, paint.blend_mode, indices);
+        }
+    }
+
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let transform = self.last_state().transform;
+        let color = [
+            u8::MAX,
+            u8::MAX,
+            u8::MAX,
+            (opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+        ];
+
+        let [w, h] = [image.width as f32 * 0.5, image.height as f32 * 0.5];
+        let geometry = &mut self.frame.geometry;
+        let base = geometry.vertices.len() as u32;
+
+        geometry.vertices.extend(
+            [
+                ([-w, -h], [0.0, 0.0]),
+                ([w, -h], [1.0, 0.0]),
+                ([w, h], [1.0, 1.0]),
+                ([-w, h], [0.0, 1.0]),
+            ]
+            .map(|(position, uv)| Vertex {
+                position: apply(&transform, position),
+                uv,
+                color,
+            }),
+        );
+
+        let start = geometry.indices.len();
+        geometry
+            .indices
+            .extend([0, 1, 2, 0, 2, 3].map(|index| base + index));
+        let indices = start..geometry.indices.len();
+
+        let material = self.push_image(image, sampler);
+        self.push_batch(material, blend_mode, indices);
+    }
+
+    fn draw_image_mesh(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        vertices: &Self::Buffer,
+        uvs: &Self::Buffer,
+        indices: &Self::Buffer,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let transform = self.last_state().transform;
+        let color = [
+            u8::MAX,
+            u8::MAX,
+            u8::MAX,
+            (opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+        ];
+
+        let vertices = vertices.as_f32_pairs();
+        let uvs = uvs.as_f32_pairs();
+        let indices = indices.as_u16s();
+        let indices = &indices[..indices.len() - indices.len() % 3];
+
+        // Malformed meshes are skipped instead of emitting out-of-bounds indices.
+        if vertices.len() != uvs.len()
+            || indices
+                .iter()
+                .any(|&index| usize::from(index) >= vertices.len())
+        {
+            return;
+        }
+
+        let geometry = &mut self.frame.geometry;
+        let base = geometry.vertices.len() as u32;
+
+        geometry
+            .vertices
+            .extend(vertices.iter().zip(uvs).map(|(&position, &uv)| Vertex {
+                position: apply(&transform, position),
+                uv,
+                color,
+            }));
+
+        let start = geometry.indices.len();
+        geometry
+            .indices
+            .extend(indices.iter().map(|&index| base + u32::from(index)));
+        let indices = start..geometry.indices.len();
+
+        let material = self.push_image(image, sampler);
+        self.push_batch(material, blend_mode, indices);
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderer")
+            .field("frame", &self.frame)
+            .field("states", &self.states)
+            .field("tolerance", &self.tolerance)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        path::FillRule,
+        renderer::{
+            Buffer as _, BufferFlags, BufferType, Gradient as _, ImageSampler, Paint as _,
+            Path as _, Renderer as _, StrokeCap, StrokeJoin,
+        },
+    };
+
+    use super::*;
+
+    const RED: renderer::Color = renderer::Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn rect(path: &mut Path, [x0, y0, x1, y1]: [f32; 4]) {
+        path.move_to(x0, y0);
+        path.line_to(x1, y0);
+        path.line_to(x1, y1);
+        path.line_to(x0, y1);
+        path.close();
+    }
+
+    fn square(size: f32) -> Path {
+        let mut path = Path::default();
+        rect(&mut path, [0.0, 0.0, size, size]);
+
+        path
+    }
+
+    fn fill(color: renderer::Color) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_color(color);
+
+        paint
+    }
+
+    fn buffer<T: bytemuck::Pod>(data: &[T]) -> Buffer {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let mut buffer = Buffer::new(BufferType::Vertex, BufferFlags::empty(), bytes.len());
+        buffer.map().copy_from_slice(bytes);
+
+        buffer
+    }
+
+    fn image(width: u32, height: u32) -> Image {
+        Image {
+            id: TextureId(NEXT_TEXTURE_ID.fetch_add(1, Ordering::Relaxed)),
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4].into(),
+        }
+    }
+
+    fn area(frame: &Frame, indices: Range<usize>) -> f32 {
+        frame.indices()[indices]
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| frame.vertices()[triangle[i] as usize].position);
+                ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() * 0.5
+            })
+            .sum()
+    }
+
+    fn total_area(frame: &Frame) -> f32 {
+        area(frame, 0..frame.indices().len())
+    }
+
+    #[test]
+    fn fill_rules() {
+        let mut path = square(10.0);
+        rect(&mut path, [2.0, 2.0, 8.0, 8.0]);
+
+        let mut renderer = Renderer::default();
+        renderer.draw_path(&path, &fill(RED));
+        assert_eq!(total_area(&renderer.take_frame()), 100.0);
+
+        path.set_fill_rule(FillRule::EvenOdd);
+        renderer.draw_path(&path, &fill(RED));
+        assert_eq!(total_area(&renderer.take_frame()), 64.0);
+    }
+
+    #[test]
+    fn stroke_joins_and_caps() {
+        let mut path = Path::default();
+        path.move_to(0.0, 0.0);
+        path.line_to(10.0, 0.0);
+        path.line_to(10.0, 10.0);
+
+        let stroke_area = |join, cap| {
+            let mut paint = fill(RED);
+            paint.set_style(renderer::PaintStyle::Stroke);
+            paint.set_thickness(2.0);
+            paint.set_join(join);
+            paint.set_cap(cap);
+
+            let mut renderer = Renderer::default();
+            renderer.draw_path(&path, &paint);
+
+            let frame = renderer.take_frame();
+            assert_eq!(frame.batches().len(), 1);
+            assert!(frame
+                .vertices()
+                .iter()
+                .all(|vertex| vertex.color == [255, 0, 0, 255]));
+
+            total_area(&frame)
+        };
+
+        for join in [StrokeJoin::Miter, StrokeJoin::Round, StrokeJoin::Bevel] {
+            let butt = stroke_area(join, StrokeCap::Butt);
+            let round = stroke_area(join, StrokeCap::Round);
+            let square = stroke_area(join, StrokeCap::Square);
+
+            // Square caps add a 1x2 rectangle to both ends, round caps a half circle.
+            assert!((square - butt - 4.0).abs() < 1e-3);
+            assert!(round > butt && round < square);
+        }
+
+        let miter = stroke_area(StrokeJoin::Miter, StrokeCap::Butt);
+        let round = stroke_area(StrokeJoin::Round, StrokeCap::Butt);
+        let bevel = stroke_area(StrokeJoin::Bevel, StrokeCap::Butt);
+
+        // The outer corner is a 1x1 square for miters and half of it for bevels.
+        assert!((miter - bevel - 0.5).abs() < 1e-3);
+        assert!(round > bevel && round < miter);
+    }
+
+    #[test]
+    fn linear_gradient_uvs() {
+        let mut paint = Paint::default();
+        paint.set_gradient(&Gradient::new_linear(
+            0.0,
+            0.0,
+            10.0,
+            0.0,
+            &[RED, RED],
+            &[0.0, 1.0],
+        ));
+
+        let mut renderer = Renderer::default();
+        renderer.transform(&[2.0, 0.0, 0.0, 2.0, 5.0, 0.0]);
+        renderer.draw_path(&square(10.0), &paint);
+
+        let frame = renderer.frame();
+        assert!(matches!(
+            &frame.batches()[0].material,
+            Material::LinearGradient(stops) if stops.len() == 2,
+        ));
+
+        // Gradient coordinates are in the space of the path, not the output space.
+        for vertex in frame.vertices() {
+            assert_eq!(vertex.uv, [(vertex.position[0] - 5.0) / 20.0, 0.0]);
+            assert_eq!(vertex.color, [u8::MAX; 4]);
+        }
+    }
+
+    #[test]
+    fn radial_gradient_uvs() {
+        let mut paint = Paint::default();
+        paint.set_gradient(&Gradient::new_radial(
+            5.0,
+            5.0,
+            5.0,
+            &[RED, RED],
+            &[0.0, 1.0],
+        ));
+
+        let mut renderer = Renderer::default();
+        renderer.draw_path(&square(10.0), &paint);
+
+        let frame = renderer.frame();
+        assert!(matches!(
+            frame.batches()[0].material,
+            Material::RadialGradient(_),
+        ));
+
+        for vertex in frame.vertices() {
+            let [x, y] = vertex.position;
+            assert_eq!(vertex.uv, [(x - 5.0) / 5.0, (y - 5.0) / 5.0]);
+        }
+    }
+
+    #[test]
+    fn image_quads() {
+        let image = image(4, 2);
+        let sampler = ImageSampler {
+            filter: renderer::ImageFilter::Nearest,
+            ..Default::default()
+        };
+
+        let mut renderer = Renderer::default();
+        renderer.transform(&[1.0, 0.0, 0.0, 1.0, 10.0, 10.0]);
+        renderer.draw_image(&image, sampler, renderer::BlendMode::SrcOver, 0.5);
+        renderer.draw_image(&image, sampler, renderer::BlendMode::SrcOver, 0.5);
+
+        let frame = renderer.frame();
+        assert_eq!(frame.images().len(), 1);
+        assert_eq!(
+            frame.batches(),
+            [Batch {
+                material: Material::Texture {
+                    id: image.id(),
+                    sampler,
+                },
+                blend_mode: renderer::BlendMode::SrcOver,
+                clip: None,
+                indices: 0..12,
+            }]
+        );
+        assert_eq!(
+            frame.vertices()[..4],
+            [
+                ([8.0, 9.0], [0.0, 0.0]),
+                ([12.0, 9.0], [1.0, 0.0]),
+                ([12.0, 11.0], [1.0, 1.0]),
+                ([8.0, 11.0], [0.0, 1.0]),
+            ]
+            .map(|(position, uv)| Vertex {
+                position,
+                uv,
+                color: [255, 255, 255, 128],
+            })
+        );
+        assert_eq!(frame.indices()[6..], [4, 5, 6, 4, 6, 7]);
+    }
+
+    #[test]
+    fn image_meshes() {
+        let image = image(2, 2);
+        let vertices = buffer(&[[0.0f32, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]]);
+        let uvs = buffer(&[[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+        let indices = buffer(&[0u16, 1, 2, 0, 2, 3]);
+
+        let mut renderer = Renderer::default();
+        renderer.draw_path(&square(1.0), &fill(RED));
+        renderer.transform(&[2.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+        renderer.draw_image_mesh(
+            &image,
+            ImageSampler::default(),
+            &vertices,
+            &uvs,
+            &indices,
+            renderer::BlendMode::Screen,
+            1.0,
+        );
+
+        let frame = renderer.frame();
+        let base = frame.vertices().len() as u32 - 4;
+        let batch = &frame.batches()[1];
+
+        assert_eq!(batch.blend_mode, renderer::BlendMode::Screen);
+        assert_eq!(
+            frame.indices()[batch.indices.clone()],
+            [0, 1, 2, 0, 2, 3].map(|index| base + index)
+        );
+        assert_eq!(frame.vertices()[base as usize + 2].position, [20.0, 20.0]);
+        assert_eq!(frame.vertices()[base as usize + 2].uv, [1.0, 1.0]);
+        assert_eq!(area(frame, batch.indices.clone()), 400.0);
+    }
+
+    #[test]
+    fn malformed_image_meshes_are_skipped() {
+        let image = image(2, 2);
+        let vertices = buffer(&[[0.0f32, 0.0], [10.0, 0.0], [10.0, 10.0]]);
+        let uvs = buffer(&[[0.0f32, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+
+        let mut renderer = Renderer::default();
+
+        for (uvs, indices) in [
+            (buffer(&[[0.0f32, 0.0]]), buffer(&[0u16, 1, 2])),
+            (uvs, buffer(&[0u16, 1, 3])),
+        ] {
+            renderer.draw_image_mesh(
+                &image,
+                ImageSampler::default(),
+                &vertices,
+                &uvs,
+                &indices,
+                renderer::BlendMode::SrcOver,
+                1.0,
+            );
+        }
+
+        assert!(renderer.frame().is_empty());
+        assert!(renderer.frame().vertices().is_empty());
+        assert!(renderer.frame().images().is_empty());
+    }
+
+    #[test]
+    fn failed_tessellation_is_dropped() {
+        let mut renderer = Renderer::default();
+        renderer.draw_path(&square(10.0), &fill(RED));
+
+        let vertices = renderer.frame().vertices().to_vec();
+        let indices = renderer.frame().indices().to_vec();
+        let batches = renderer.frame().batches().to_vec();
+
+        // lyon's fill tessellator rejects NaN tolerances.
+        let tolerance = renderer.tolerance;
+        renderer.tolerance = f32::NAN;
+        renderer.draw_path(&square(10.0), &fill(RED));
+        renderer.state_push();
+        renderer.set_clip(&square(10.0));
+
+        assert_eq!(renderer.frame().vertices(), vertices);
+        assert_eq!(renderer.frame().indices(), indices);
+        assert_eq!(renderer.frame().batches(), batches);
+
+        // The failed clip is kept empty so that it clips everything drawn with it.
+        let end = indices.len();
+        assert_eq!(
+            renderer.frame().clips(),
+            [Clip {
+                parent: None,
+                indices: end..end,
+            }],
+        );
+
+        renderer.tolerance = tolerance;
+        renderer.draw_path(&square(10.0), &fill(RED));
+        assert_eq!(renderer.frame().batches().last().unwrap().clip, Some(0));
+    }
+
+    #[test]
+    fn batches() {
+        let path = square(10.0);
+        let red = fill(RED);
+
+        let mut multiply = fill(RED);
+        multiply.set_blend_mode(renderer::BlendMode::Multiply);
+
+        let mut gradient = Paint::default();
+        gradient.set_gradient(&Gradient::new_linear(
+            0.0,
+            0.0,
+            10.0,
+            0.0,
+            &[RED, RED],
+            &[0.0, 1.0],
+        ));
+
+        let mut renderer = Renderer::default();
+        renderer.draw_path(&path, &red);
+        renderer.draw_path(&path, &red);
+        renderer.draw_path(&path, &multiply);
+        renderer.draw_path(&path, &gradient);
+
+        renderer.state_push();
+        renderer.set_clip(&path);
+        renderer.draw_path(&path, &gradient);
+        renderer.state_push();
+        renderer.set_clip(&path);
+        renderer.draw_path(&path, &gradient);
+        renderer.state_pop();
+        renderer.state_pop();
+
+        renderer.draw_path(&path, &gradient);
+
+        let frame = renderer.frame();
+        let summary: Vec<_> = frame
+            .batches()
+            .iter()
+            .map(|batch| {
+                (
+                    matches!(batch.material, Material::Solid),
+                    batch.blend_mode,
+                    batch.clip,
+                    batch.indices.len() / 6,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (true, renderer::BlendMode::SrcOver, None, 2),
+                (true, renderer::BlendMode::Multiply, None, 1),
+                (false, renderer::BlendMode::SrcOver, None, 1),
+                (false, renderer::BlendMode::SrcOver, Some(0), 1),
+                (false, renderer::BlendMode::SrcOver, Some(1), 1),
+                (false, renderer::BlendMode::SrcOver, None, 1),
+            ]
+        );
+        assert_eq!(
+            frame
+                .clips()
+                .iter()
+                .map(|clip| clip.parent)
+                .collect::<Vec<_>>(),
+            [None, Some(0)]
+        );
+    }
+
+    #[test]
+    fn take_frame_and_reset() {
+        let mut renderer = Renderer::default();
+
+        renderer.state_push();
+        renderer.transform(&[1.0, 0.0, 0.0, 1.0, 100.0, 0.0]);
+        renderer.set_clip(&square(10.0));
+        renderer.draw_path(&square(10.0), &fill(RED));
+
+        let frame = renderer.take_frame();
+        assert_eq!(frame.batches().len(), 1);
+        assert_eq!(frame.clips().len(), 1);
+        assert!(renderer.frame().is_empty());
+        assert!(renderer.frame().vertices().is_empty());
+
+        // The transform and clip of the unfinished state don't leak into the next frame.
+        renderer.draw_path(&square(10.0), &fill(RED));
+        assert_eq!(renderer.frame().batches()[0].clip, None);
+        assert!(renderer
+            .frame()
+            .vertices()
+            .iter()
+            .all(|vertex| vertex.position[0] <= 10.0));
+
+        renderer.state_push();
+        renderer.set_clip(&square(10.0));
+        renderer.reset();
+
+        assert!(renderer.frame().is_empty());
+        assert!(renderer.frame().clips().is_empty());
+        assert!(renderer.frame().indices().is_empty());
+
+        renderer.draw_path(&square(10.0), &fill(RED));
+        assert_eq!(renderer.frame().batches()[0].clip, None);
+    }
+}
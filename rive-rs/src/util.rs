@@ -0,0 +1,273 @@
+#[cfg(any(feature = "software", feature = "vello"))]
+use crate::renderer::ImageWrap;
+
+/// Finds the affine transform that maps triangle `from` to triangle `to`. The algorithm is based
+/// on the [Simplex Affine Mapping] method which has a [Swift implementation]. The result is in the
+/// same `[xx, xy, yx, yy, tx, ty]` layout as the transforms passed to
+/// [`Renderer::transform`](crate::renderer::Renderer::transform).
+///
+/// [Simplex Affine Mapping]: https://www.researchgate.net/publication/332410209_Beginner%27s_guide_to_mapping_simplexes_affinely
+/// [Swift implementation]: https://rethunk.medium.com/finding-an-affine-transform-using-three-2d-point-correspondences-using-simplex-affine-mapping-255aeb4e8055
+fn simplex_affine_mapping(from: [[f32; 2]; 3], to: [[f32; 2]; 3]) -> [f32; 6] {
+    let [[ax, ay], [bx, by], [cx, cy]] = from;
+    let [d, e, f] = to;
+
+    let det_recip = (ax * by + bx * cy + cx * ay - ax * cy - bx * ay - cx * by).recip();
+
+    let combine = |wd: f32, we: f32, wf: f32| {
+        [
+            (d[0] * wd + e[0] * we + f[0] * wf) * det_recip,
+            (d[1] * wd + e[1] * we + f[1] * wf) * det_recip,
+        ]
+    };
+
+    let p = combine(by - cy, -(ay - cy), ay - by);
+    let q = combine(-(bx - cx), ax - cx, -(ax - bx));
+    let t = combine(bx * cy - by * cx, -(ax * cy - ay * cx), ax * by - ay * bx);
+
+    [p[0], p[1], q[0], q[1], t[0], t[1]]
+}
+
+/// Finds the transform that maps the pixels of a `width`x`height` image, addressed by the
+/// normalized `uvs`, onto the triangle described by `points`.
+pub fn map_uvs_to_triangle(
+    points: &[[f32; 2]; 3],
+    uvs: &[[f32; 2]; 3],
+    width: u32,
+    height: u32,
+) -> [f32; 6] {
+    simplex_affine_mapping(
+        uvs.map(|uv| [uv[0] * width as f32, uv[1] * height as f32]),
+        *points,
+    )
+}
+
+/// Most image repetitions a triangle is split into along one axis. Triangles whose UVs span more
+/// repetitions are clamped instead.
+#[cfg(any(feature = "software", feature = "vello"))]
+const MAX_WRAPPED_TILES: f32 = 256.0;
+
+/// Part of an axis of UV space that is sampled from a single chunk of a single repetition of an
+/// image.
+#[cfg(any(feature = "software", feature = "vello"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvCell {
+    /// UV coordinate of the start of the repetition.
+    pub tile: f32,
+    /// Whether the repetition is mirrored, i.e. goes from `tile + 1.0` back to `tile`.
+    pub mirrored: bool,
+    /// Index of the chunk of the image.
+    pub chunk: usize,
+}
+
+/// Splits the UV range `start..end` of one axis into cells, where the image repeats according to
+/// `wrap` and every repetition is cut into `chunks` chunks that are `chunk_len` long in UV space.
+///
+/// Returns the cuts between the cells in ascending order and the cells themselves, which are one
+/// more than the cuts. The first and last cells extend infinitely.
+#[cfg(any(feature = "software", feature = "vello"))]
+pub fn uv_cells(
+    wrap: ImageWrap,
+    start: f32,
+    end: f32,
+    chunk_len: f32,
+    chunks: usize,
+) -> (Vec<f32>, Vec<UvCell>) {
+    let chunk_cuts = (1..chunks).map(|chunk| chunk as f32 * chunk_len);
+
+    let first_tile = start.floor();
+    let last_tile = (end.ceil() - 1.0).max(first_tile);
+
+    let tiles = last_tile - first_tile;
+
+    if wrap == ImageWrap::Clamp || tiles.is_nan() || tiles >= MAX_WRAPPED_TILES {
+        let cells = (0..chunks).map(|chunk| UvCell {
+            tile: 0.0,
+            mirrored: false,
+            chunk,
+        });
+
+        return trim_cells(chunk_cuts.collect(), cells.collect(), start, end);
+    }
+
+    let mut cuts = Vec::new();
+    let mut cells = Vec::new();
+
+    let mut tile = first_tile;
+    while tile <= last_tile {
+        let mirrored = wrap == ImageWrap::Mirror && tile.rem_euclid(2.0) == 1.0;
+
+        if !cells.is_empty() {
+            cuts.push(tile);
+        }
+
+        if mirrored {
+            cuts.extend(chunk_cuts.clone().rev().map(|cut| tile + 1.0 - cut));
+        } else {
+            cuts.extend(chunk_cuts.clone().map(|cut| tile + cut));
+        }
+
+        cells.extend((0..chunks).map(|i| UvCell {
+            tile,
+            mirrored,
+            chunk: if mirrored { chunks - 1 - i } else { i },
+        }));
+
+        tile += 1.0;
+    }
+
+    trim_cells(cuts, cells, start, end)
+}
+
+/// Drops the cells that lie outside of `start..end`, letting the remaining first and last cells
+/// extend infinitely.
+#[cfg(any(feature = "software", feature = "vello"))]
+fn trim_cells(
+    mut cuts: Vec<f32>,
+    mut cells: Vec<UvCell>,
+    start: f32,
+    end: f32,
+) -> (Vec<f32>, Vec<UvCell>) {
+    let first = cuts.iter().take_while(|&&cut| cut <= start).count();
+    let last = cuts.iter().take_while(|&&cut| cut < end).count();
+
+    cuts.truncate(last);
+    cuts.drain(..first.min(last));
+    cells.truncate(last + 1);
+    cells.drain(..first.min(last));
+
+    (cuts, cells)
+}
+
+/// Vertex of a part of a triangle that was split by [`split_triangle`].
+#[cfg(any(feature = "software", feature = "vello"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SplitVertex {
+    pub point: [f32; 2],
+    pub uv: [f32; 2],
+    /// Flag of the edge that starts at this vertex.
+    pub flag: bool,
+    /// Triangle edge that the edge starting at this vertex lies on, if it doesn't lie on a cut.
+    edge: Option<usize>,
+}
+
+/// Clips `polygon`, a part of `triangle`, to the side of the line `uv[axis] == bound` that is
+/// below the line if `below` is set. Edges along the line get `flag`.
+///
+/// New vertices only depend on the edge of the triangle or the cut that they lie on, so that
+/// neighboring parts share them exactly.
+#[cfg(any(feature = "software", feature = "vello"))]
+fn clip_polygon(
+    polygon: &[SplitVertex],
+    triangle: &[SplitVertex; 3],
+    uv_transform: &[f32; 6],
+    axis: usize,
+    bound: f32,
+    below: bool,
+    flag: bool,
+) -> Vec<SplitVertex> {
+    let inside =
+        |vertex: &SplitVertex| (vertex.uv[axis] <= bound) == below || vertex.uv[axis] == bound;
+    let intersect = |vertex: &SplitVertex, flag: bool, edge: Option<usize>| {
+        let (uv, point) = match vertex.edge {
+            Some(i) => {
+                let mut a = &triangle[i];
+                let mut b = &triangle[(i + 1) % 3];
+                if a.uv > b.uv {
+                    (a, b) = (b, a);
+                }
+
+                let t = (bound - a.uv[axis]) / (b.uv[axis] - a.uv[axis]);
+                let lerp =
+                    |a: [f32; 2], b: [f32; 2]| [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])];
+
+                (lerp(a.uv, b.uv), lerp(a.point, b.point))
+            }
+            None => {
+                let mut uv = vertex.uv;
+                uv[axis] = bound;
+
+                let [a, b, c, d, e, f] = *uv_transform;
+                (uv, [a * uv[0] + c * uv[1] + e, b * uv[0] + d * uv[1] + f])
+            }
+        };
+
+        let mut uv = uv;
+        uv[axis] = bound;
+
+        SplitVertex {
+            point,
+            uv,
+            flag,
+            edge,
+        }
+    };
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, vertex) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+
+        match (inside(vertex), inside(next)) {
+            (true, true) => clipped.push(*vertex),
+            (true, false) => {
+                clipped.push(*vertex);
+                clipped.push(intersect(vertex, flag, None));
+            }
+            (false, true) => clipped.push(intersect(vertex, vertex.flag, vertex.edge)),
+            (false, false) => {}
+        }
+    }
+
+    clipped
+}
+
+/// Cuts the triangle of `points` and `uvs` along the UV `cuts` of both axes, as returned by
+/// [`uv_cells`], and calls `f` with the index of the cell of every part and the vertices of the
+/// part.
+///
+/// Parts are visited row by row. The edges of the parts are flagged if they are edges of the
+/// triangle flagged in `flags` or if they are shared with parts that are visited later.
+#[cfg(any(feature = "software", feature = "vello"))]
+pub fn split_triangle(
+    points: [[f32; 2]; 3],
+    uvs: [[f32; 2]; 3],
+    flags: [bool; 3],
+    cuts: [&[f32]; 2],
+    mut f: impl FnMut([usize; 2], &[SplitVertex]),
+) {
+    let triangle = [0, 1, 2].map(|i| SplitVertex {
+        point: points[i],
+        uv: uvs[i],
+        flag: flags[i],
+        edge: Some(i),
+    });
+    let uv_transform = map_uvs_to_triangle(&points, &uvs, 1, 1);
+    let clip = |polygon: &[SplitVertex], axis, bound, below| {
+        clip_polygon(polygon, &triangle, &uv_transform, axis, bound, below, below)
+    };
+
+    for y in 0..=cuts[1].len() {
+        let mut row = triangle.to_vec();
+        if let Some(&cut) = y.checked_sub(1).and_then(|i| cuts[1].get(i)) {
+            row = clip(&row, 1, cut, false);
+        }
+        if let Some(&cut) = cuts[1].get(y) {
+            row = clip(&row, 1, cut, true);
+        }
+
+        for x in 0..=cuts[0].len() {
+            let mut part = row.clone();
+            if let Some(&cut) = x.checked_sub(1).and_then(|i| cuts[0].get(i)) {
+                part = clip(&part, 0, cut, false);
+            }
+            if let Some(&cut) = cuts[0].get(x) {
+                part = clip(&part, 0, cut, true);
+            }
+
+            if part.len() >= 3 {
+                f([x, y], &part);
+            }
+        }
+    }
+}
@@ -1,20 +1,24 @@
-use std::{fmt, io::Cursor};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Cursor,
+    ops::Range,
+    sync::{Mutex, OnceLock, PoisonError},
+};
 
 use image::io::Reader;
 use smallvec::SmallVec;
 use vello::{
-    kurbo::{Affine, BezPath, Line, PathSeg, Point, Rect, Shape, Vec2},
+    kurbo::{Affine, BezPath, PathEl, Point, Rect, Shape, Vec2},
     peniko::{
-        self, BlendMode, Brush, BrushRef, Cap, Color, ColorStop, ColorStopsSource, Fill, Format,
-        Join, Mix, Stroke,
+        self, BlendMode, Brush, Cap, Color, ColorStop, ColorStopsSource, Fill, Format, Join, Mix,
+        Stroke,
     },
     SceneBuilder, SceneFragment,
 };
 
 mod util;
 
-use util::ScaleFromOrigin;
-
 use crate::renderer;
 
 fn to_vello_color(color: renderer::Color) -> Color {
@@ -42,15 +46,19 @@ fn to_vello_mix(blend_mode: renderer::BlendMode) -> Mix {
     }
 }
 
-fn triangle_path(points: [Point; 3]) -> BezPath {
-    BezPath::from_path_segments(
-        [
-            PathSeg::Line(Line::new(points[0], points[1])),
-            PathSeg::Line(Line::new(points[1], points[2])),
-            PathSeg::Line(Line::new(points[2], points[0])),
-        ]
-        .into_iter(),
-    )
+/// Points and UVs of a mesh triangle.
+type Triangle = ([[f32; 2]; 3], [[f32; 2]; 3]);
+
+/// Identifies an edge between two mesh vertices regardless of its direction.
+fn edge_key(a: [f32; 2], b: [f32; 2]) -> [u32; 4] {
+    let a = a.map(f32::to_bits);
+    let b = b.map(f32::to_bits);
+
+    if a <= b {
+        [a[0], a[1], b[0], b[1]]
+    } else {
+        [b[0], b[1], a[0], a[1]]
+    }
 }
 
 #[derive(Debug)]
@@ -91,10 +99,51 @@ impl renderer::Buffer for Buffer {
     fn unmap(&mut self) {}
 }
 
+/// Maximum distance in pixels between even-odd clip paths and their flattened non-zero version.
+const CLIP_TOLERANCE: f64 = 0.1;
+
 #[derive(Debug)]
 pub struct Path {
     inner: BezPath,
     fill: Fill,
+    /// Non-zero version of the path for even-odd clips, together with the tolerance it was
+    /// flattened with. It is dropped whenever the path changes.
+    non_zero: Mutex<Option<(f64, BezPath)>>,
+}
+
+impl Path {
+    fn invalidate(&mut self) {
+        *self
+            .non_zero
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Calls `f` with the non-zero version of the path, flattened finely enough to look smooth
+    /// when drawn with `transform`. It is only converted again once the path changes or the
+    /// scale of `transform` crosses a power of two.
+    fn with_non_zero<T>(&self, transform: Affine, f: impl FnOnce(&BezPath) -> T) -> T {
+        let scale = transform.determinant().abs().sqrt();
+        // A transform that collapses the path leaves nothing to clip to at any tolerance.
+        let tolerance = if scale > 0.0 {
+            CLIP_TOLERANCE / scale
+        } else {
+            CLIP_TOLERANCE
+        };
+        let tolerance = 2.0f64.powi(tolerance.log2().floor() as i32);
+
+        let mut non_zero = self.non_zero.lock().unwrap_or_else(PoisonError::into_inner);
+        match &*non_zero {
+            Some((cached_tolerance, path)) if *cached_tolerance == tolerance => f(path),
+            _ => {
+                let path = util::even_odd_to_non_zero(&self.inner, tolerance);
+                let result = f(&path);
+                *non_zero = Some((tolerance, path));
+
+                result
+            }
+        }
+    }
 }
 
 impl Default for Path {
@@ -103,6 +152,7 @@ impl Default for Path {
         Self {
             inner: Default::default(),
             fill: Fill::NonZero,
+            non_zero: Mutex::new(None),
         }
     }
 }
@@ -134,6 +184,7 @@ impl renderer::Path for Path {
 
     #[inline]
     fn reset(&mut self) {
+        self.invalidate();
         self.inner.truncate(0);
     }
 
@@ -142,6 +193,7 @@ impl renderer::Path for Path {
         let mut from = from.inner.clone();
         from.apply_affine(Affine::new(transform.map(Into::into)));
 
+        self.invalidate();
         self.inner.extend(from.elements().iter().cloned());
     }
 
@@ -155,16 +207,19 @@ impl renderer::Path for Path {
 
     #[inline]
     fn move_to(&mut self, x: f32, y: f32) {
+        self.invalidate();
         self.inner.move_to(Point::new(x as f64, y as f64));
     }
 
     #[inline]
     fn line_to(&mut self, x: f32, y: f32) {
+        self.invalidate();
         self.inner.line_to(Point::new(x as f64, y as f64));
     }
 
     #[inline]
     fn cubic_to(&mut self, ox: f32, oy: f32, ix: f32, iy: f32, x: f32, y: f32) {
+        self.invalidate();
         self.inner.curve_to(
             Point::new(ox as f64, oy as f64),
             Point::new(ix as f64, iy as f64),
@@ -174,6 +229,7 @@ impl renderer::Path for Path {
 
     #[inline]
     fn close(&mut self) {
+        self.invalidate();
         self.inner.close_path();
     }
 }
@@ -334,9 +390,149 @@ impl renderer::Gradient for Gradient {
     }
 }
 
+/// Largest side of the images that are handed to vello when emulating
+/// [`renderer::ImageFilter::Nearest`], so that each of them fits into vello's image atlas.
+const MAX_CHUNK_SIZE: u32 = 4096;
+/// Number of pixels up to which upscaled copies grow beyond twice the size of their image. A copy
+/// of this size takes up 64 MiB.
+const MAX_NEAREST_PIXELS: u64 = 4096 * 4096;
+const MAX_NEAREST_SCALE: u32 = 8;
+
+/// Part of an image that vello draws on its own.
+#[derive(Clone, Debug)]
+struct Chunk {
+    image: peniko::Image,
+    /// Position of the chunk's top-left pixel in the original image.
+    origin: [u32; 2],
+}
+
+/// An image as it is drawn with one filter.
+#[derive(Debug)]
+struct Sampled {
+    /// Chunks in row-major order. Every chunk covers `chunk_size` pixels of the original image,
+    /// except for the last ones of each row and column.
+    chunks: Vec<Chunk>,
+    columns: usize,
+    chunk_size: u32,
+    /// Number of pixels of the chunks per pixel of the original image.
+    scale: u32,
+}
+
+impl Sampled {
+    fn new(image: &peniko::Image) -> Self {
+        Self {
+            chunks: vec![Chunk {
+                image: image.clone(),
+                origin: [0, 0],
+            }],
+            columns: 1,
+            chunk_size: image.width.max(image.height).max(1),
+            scale: 1,
+        }
+    }
+
+    /// Upscales `image` by an integer factor by repeating its pixels. Sampling the copy
+    /// bilinearly only blurs a thin band around each original pixel, which looks like nearest
+    /// filtering.
+    ///
+    /// Copies are at least twice as large as the image, which takes up four times its memory.
+    /// Copies that are too large for vello's image atlas are cut into chunks.
+    fn upscale_nearest(image: &peniko::Image) -> Self {
+        let pixels = image.width as u64 * image.height as u64;
+        let scale = (2..=MAX_NEAREST_SCALE)
+            .rev()
+            .find(|&scale| pixels * (scale * scale) as u64 <= MAX_NEAREST_PIXELS)
+            .unwrap_or(2);
+        let chunk_size = MAX_CHUNK_SIZE / scale;
+
+        let mut chunks = Vec::new();
+        for y in (0..image.height).step_by(chunk_size as usize) {
+            for x in (0..image.width).step_by(chunk_size as usize) {
+                let width = chunk_size.min(image.width - x);
+                let height = chunk_size.min(image.height - y);
+
+                chunks.push(Chunk {
+                    image: upscale_region(image, x, y, width, height, scale),
+                    origin: [x, y],
+                });
+            }
+        }
+
+        Self {
+            chunks,
+            columns: (0..image.width).step_by(chunk_size as usize).len(),
+            chunk_size,
+            scale,
+        }
+    }
+}
+
+/// Copies the `width`x`height` region of `image` at `x`, `y`, repeating every pixel `scale` times
+/// in both directions.
+fn upscale_region(
+    image: &peniko::Image,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    scale: u32,
+) -> peniko::Image {
+    let row_len = (width * scale) as usize * 4;
+    let image_row_len = image.width as usize * 4;
+
+    let mut data = Vec::with_capacity(row_len * (height * scale) as usize);
+    for row in image
+        .data
+        .data()
+        .chunks_exact(image_row_len)
+        .skip(y as usize)
+        .take(height as usize)
+    {
+        let start = data.len();
+
+        for pixel in row[x as usize * 4..(x + width) as usize * 4].chunks_exact(4) {
+            for _ in 0..scale {
+                data.extend_from_slice(pixel);
+            }
+        }
+
+        for _ in 1..scale {
+            data.extend_from_within(start..start + row_len);
+        }
+    }
+
+    peniko::Image::new(data.into(), Format::Rgba8, width * scale, height * scale)
+}
+
 #[derive(Debug)]
 pub struct Image {
     inner: peniko::Image,
+    bilinear: Sampled,
+    nearest: OnceLock<Sampled>,
+}
+
+impl Image {
+    fn new(inner: peniko::Image) -> Self {
+        Self {
+            bilinear: Sampled::new(&inner),
+            inner,
+            nearest: OnceLock::new(),
+        }
+    }
+
+    /// Returns the image to draw with `filter`.
+    ///
+    /// This revision of vello always samples images bilinearly and has no mipmaps, so nearest
+    /// filtering is only emulated with an upscaled copy of the image that is created the first
+    /// time it is needed and kept alongside the original.
+    fn sampled(&self, filter: renderer::ImageFilter) -> &Sampled {
+        match filter {
+            renderer::ImageFilter::Nearest => self
+                .nearest
+                .get_or_init(|| Sampled::upscale_nearest(&self.inner)),
+            renderer::ImageFilter::Bilinear | renderer::ImageFilter::Mipmap => &self.bilinear,
+        }
+    }
 }
 
 impl renderer::Image for Image {
@@ -350,59 +546,405 @@ impl renderer::Image for Image {
         let width = image.width();
         let height = image.height();
 
-        Some(Image {
-            inner: peniko::Image::new(image.into_raw().into(), Format::Rgba8, width, height),
-        })
+        Some(Image::new(peniko::Image::new(
+            image.into_raw().into(),
+            Format::Rgba8,
+            width,
+            height,
+        )))
     }
 }
 
+/// A recorded [`SceneBuilder`] call. Shapes are ranges of `Renderer::elements`.
+#[derive(Clone, Debug)]
+enum Command {
+    PushLayer {
+        blend_mode: BlendMode,
+        alpha: f32,
+        transform: Affine,
+        shape: Range<usize>,
+    },
+    PopLayer,
+    Fill {
+        fill: Fill,
+        transform: Affine,
+        brush: Brush,
+        brush_transform: Option<Affine>,
+        shape: Range<usize>,
+    },
+    Stroke {
+        stroke: Stroke,
+        transform: Affine,
+        brush: Brush,
+        shape: Range<usize>,
+    },
+    DrawImage {
+        image: peniko::Image,
+        transform: Affine,
+    },
+}
+
+/// Records the draw calls of a frame, which can then be appended to any [`SceneBuilder`].
+///
+/// The renderer is meant to be reused across frames: [`Renderer::reset`] clears the recorded
+/// frame but keeps the allocated buffers.
+///
+/// ```
+/// # use rive_rs::{
+/// #     renderer::{Paint as _, Path as _, Renderer as _},
+/// #     vello::{Paint, Path, Renderer},
+/// # };
+/// # use vello::{kurbo::Affine, Scene, SceneBuilder};
+/// let mut renderer = Renderer::default();
+/// let mut scene = Scene::default();
+///
+/// let mut path = Path::default();
+/// path.move_to(0.0, 0.0);
+/// path.line_to(10.0, 0.0);
+/// path.line_to(0.0, 10.0);
+/// path.close();
+///
+/// for frame in 0..3 {
+///     renderer.reset();
+///
+///     renderer.state_push();
+///     renderer.transform(&[1.0, 0.0, 0.0, 1.0, frame as f32, 0.0]);
+///     renderer.set_clip(&path);
+///     renderer.draw_path(&path, &Paint::default());
+///     renderer.state_pop();
+///
+///     let mut builder = SceneBuilder::for_scene(&mut scene);
+///     renderer.append_to(&mut builder, Some(Affine::scale(2.0)));
+/// }
+/// ```
 pub struct Renderer {
-    scene: Box<SceneFragment>,
-    builder: SceneBuilder<'static>,
+    commands: Vec<Command>,
+    /// Elements of all the shapes recorded by `commands`.
+    elements: Vec<PathEl>,
     transforms: Vec<Affine>,
-    clips: Vec<bool>,
+    /// Index of the state every clip layer was pushed in.
+    clips: Vec<usize>,
 }
 
 impl Renderer {
-    pub fn scene(&self) -> &SceneFragment {
-        &self.scene
+    /// Clears the recorded frame and resets the transform and clips, keeping the allocated
+    /// buffers for the next frame.
+    pub fn reset(&mut self) {
+        self.commands.clear();
+        self.elements.clear();
+        self.transforms.clear();
+        self.transforms.push(Affine::IDENTITY);
+        self.clips.clear();
+    }
+
+    /// Appends the recorded frame to `builder`, transformed by `transform`. Clips that are still
+    /// set are closed at the end of the frame.
+    ///
+    /// Every call encodes the whole frame again. To draw the same frame several times, append it
+    /// to a [`SceneFragment`] once and append that fragment instead.
+    pub fn append_to(&self, builder: &mut SceneBuilder<'_>, transform: Option<Affine>) {
+        let base = transform.unwrap_or(Affine::IDENTITY);
+        let shape = |range: &Range<usize>| &self.elements[range.clone()];
+
+        let open_clips = self.clips.iter().map(|_| &Command::PopLayer);
+
+        for command in self.commands.iter().chain(open_clips) {
+            match command {
+                Command::PushLayer {
+                    blend_mode,
+                    alpha,
+                    transform,
+                    shape: range,
+                } => builder.push_layer(*blend_mode, *alpha, base * *transform, &shape(range)),
+                Command::PopLayer => builder.pop_layer(),
+                Command::Fill {
+                    fill,
+                    transform,
+                    brush,
+                    brush_transform,
+                    shape: range,
+                } => builder.fill(
+                    *fill,
+                    base * *transform,
+                    brush,
+                    *brush_transform,
+                    &shape(range),
+                ),
+                Command::Stroke {
+                    stroke,
+                    transform,
+                    brush,
+                    shape: range,
+                } => builder.stroke(stroke, base * *transform, brush, None, &shape(range)),
+                Command::DrawImage { image, transform } => {
+                    builder.draw_image(image, base * *transform)
+                }
+            }
+        }
     }
 
     pub fn into_scene(self) -> SceneFragment {
-        *self.scene
+        let mut scene = SceneFragment::default();
+        self.append_to(&mut SceneBuilder::for_fragment(&mut scene), None);
+
+        scene
     }
 
     fn last_transform(&mut self) -> &mut Affine {
         self.transforms.last_mut().unwrap()
     }
 
-    fn last_clip(&mut self) -> &mut bool {
-        self.clips.last_mut().unwrap()
+    fn push_shape(&mut self, shape: &impl Shape) -> Range<usize> {
+        let start = self.elements.len();
+        self.elements.extend(shape.path_elements(0.1));
+
+        start..self.elements.len()
+    }
+
+    fn push_layer(
+        &mut self,
+        blend_mode: impl Into<BlendMode>,
+        alpha: f32,
+        transform: Affine,
+        shape: &impl Shape,
+    ) {
+        let shape = self.push_shape(shape);
+
+        self.commands.push(Command::PushLayer {
+            blend_mode: blend_mode.into(),
+            alpha,
+            transform,
+            shape,
+        });
+    }
+
+    /// Fills `triangles` of local points and their UVs with `image`.
+    fn push_image_triangles(
+        &mut self,
+        image: &Image,
+        sampler: renderer::ImageSampler,
+        triangles: &[Triangle],
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let sampled = image.sampled(sampler.filter);
+        let size = [image.inner.width, image.inner.height];
+
+        if sampled.chunks.is_empty() {
+            return;
+        }
+
+        let transform = *self.last_transform();
+        let [a, b, c, d, _, _] = transform.as_coeffs();
+        let det = a * d - b * c;
+
+        if det == 0.0 {
+            return;
+        }
+
+        // Largest distance a device pixel spans in the mesh's coordinate space, i.e. the
+        // reciprocal of the smallest singular value of the transform.
+        let sum = a * a + b * b + c * c + d * d;
+        let pixel = (0.5 * (sum - (sum * sum - 4.0 * det * det).max(0.0).sqrt()))
+            .sqrt()
+            .recip();
+
+        // Index of the last triangle that contains each edge.
+        let mut last_triangles = HashMap::new();
+        for (i, (points, _)) in triangles.iter().enumerate() {
+            for j in 0..3 {
+                last_triangles.insert(edge_key(points[j], points[(j + 1) % 3]), i);
+            }
+        }
+
+        let wraps = [sampler.wrap_x, sampler.wrap_y];
+        let chunk_lens = size.map(|size| sampled.chunk_size as f32 / size as f32);
+        let chunk_counts = [sampled.columns, sampled.chunks.len() / sampled.columns];
+
+        let mix = to_vello_mix(blend_mode);
+        let skip_blending = mix == Mix::Normal && opacity == 1.0;
+
+        let layer_index = self.commands.len();
+        let mut bounds: Option<Rect> = None;
+
+        for (i, (points, uvs)) in triangles.iter().enumerate() {
+            let corners = points.map(|v| Point::new(v[0] as f64, v[1] as f64));
+
+            if (corners[1] - corners[0]).cross(corners[2] - corners[0]) == 0.0 {
+                continue;
+            }
+
+            // Maps UV space onto the triangle.
+            let uv_transform = util::map_uvs_to_triangle(points, uvs, 1, 1);
+
+            if !uv_transform.determinant().is_normal() {
+                continue;
+            }
+
+            let triangle_bounds = Rect::from_points(corners[0], corners[1])
+                .union_pt(corners[2])
+                .inflate(pixel, pixel);
+            bounds = Some(bounds.map_or(triangle_bounds, |bounds| bounds.union(triangle_bounds)));
+
+            // Triangles are drawn over each other in order, like in the original renderer. Edges
+            // shared with a later triangle are pushed out by a pixel below it, so that its
+            // anti-aliased edge is drawn over this triangle instead of over a seam.
+            let outset =
+                [0, 1, 2].map(|j| last_triangles[&edge_key(points[j], points[(j + 1) % 3])] > i);
+
+            // This revision of vello clamps images to their edges, so wrapped images are drawn
+            // one repetition at a time. The same goes for the chunks of large images.
+            let [(x_cuts, x_cells), (y_cuts, y_cells)] = [0, 1].map(|axis| {
+                let (start, end) = uvs
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(start, end), uv| {
+                        (start.min(uv[axis]), end.max(uv[axis]))
+                    });
+
+                crate::util::uv_cells(
+                    wraps[axis],
+                    start,
+                    end,
+                    chunk_lens[axis],
+                    chunk_counts[axis],
+                )
+            });
+
+            crate::util::split_triangle(
+                *points,
+                *uvs,
+                outset,
+                [&x_cuts, &y_cuts],
+                |[x, y], part| {
+                    let cells = [x_cells[x], y_cells[y]];
+                    let chunk = &sampled.chunks[cells[1].chunk * sampled.columns + cells[0].chunk];
+
+                    // Maps the pixels of the chunk to UV space, one axis at a time.
+                    let [(x_scale, x_offset), (y_scale, y_offset)] = [0, 1].map(|axis| {
+                        let size = size[axis] as f64;
+                        let origin = chunk.origin[axis] as f64 / size;
+                        let scale = (sampled.scale as f64 * size).recip();
+                        let tile = cells[axis].tile as f64;
+
+                        if cells[axis].mirrored {
+                            (-scale, tile + 1.0 - origin)
+                        } else {
+                            (scale, tile + origin)
+                        }
+                    });
+
+                    let part_points: Vec<_> = part
+                        .iter()
+                        .map(|vertex| Point::new(vertex.point[0] as f64, vertex.point[1] as f64))
+                        .collect();
+                    let part_outset: Vec<_> = part.iter().map(|vertex| vertex.flag).collect();
+
+                    let start = self.elements.len();
+                    self.elements
+                        .extend(util::outset_polygon(&part_points, &part_outset, pixel));
+
+                    self.commands.push(Command::Fill {
+                        fill: Fill::NonZero,
+                        transform,
+                        brush: Brush::Image(chunk.image.clone()),
+                        brush_transform: Some(
+                            uv_transform
+                                * Affine::new([x_scale, 0.0, 0.0, y_scale, x_offset, y_offset]),
+                        ),
+                        shape: start..self.elements.len(),
+                    });
+                },
+            );
+        }
+
+        let Some(bounds) = bounds else {
+            return;
+        };
+
+        if !skip_blending {
+            // The whole mesh is blended at once, so that overlapping triangles are drawn over
+            // each other before the blend mode and opacity are applied.
+            let shape = self.push_shape(&bounds);
+
+            self.commands.insert(
+                layer_index,
+                Command::PushLayer {
+                    blend_mode: mix.into(),
+                    alpha: opacity,
+                    transform,
+                    shape,
+                },
+            );
+            self.commands.push(Command::PopLayer);
+        }
+    }
+
+    /// Returns the number of clips that currently restrict drawing.
+    ///
+    /// Like in rive-cpp, every [`set_clip`](renderer::Renderer::set_clip) intersects the
+    /// current clip, and clips last until the [`state_pop`](renderer::Renderer::state_pop)
+    /// matching the [`state_push`](renderer::Renderer::state_push) they were set after.
+    ///
+    /// ```
+    /// # use rive_rs::{
+    /// #     path::FillRule,
+    /// #     renderer::{BlendMode, Paint as _, Path as _, Renderer as _},
+    /// #     vello::{Paint, Path, Renderer},
+    /// # };
+    /// let mut renderer = Renderer::default();
+    ///
+    /// let mut path = Path::default();
+    /// path.move_to(0.0, 0.0);
+    /// path.line_to(10.0, 0.0);
+    /// path.line_to(0.0, 10.0);
+    /// path.close();
+    ///
+    /// let mut even_odd_path = Path::default();
+    /// even_odd_path.extend(&path, &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    /// even_odd_path.set_fill_rule(FillRule::EvenOdd);
+    ///
+    /// let mut paint = Paint::default();
+    /// paint.set_blend_mode(BlendMode::Multiply);
+    ///
+    /// renderer.state_push();
+    /// renderer.set_clip(&path);
+    /// assert_eq!(renderer.clip_depth(), 1);
+    ///
+    /// renderer.state_push();
+    /// renderer.set_clip(&path);
+    /// renderer.set_clip(&even_odd_path);
+    /// renderer.draw_path(&path, &paint);
+    /// assert_eq!(renderer.clip_depth(), 3);
+    ///
+    /// renderer.state_pop();
+    /// assert_eq!(renderer.clip_depth(), 1);
+    ///
+    /// renderer.state_pop();
+    /// assert_eq!(renderer.clip_depth(), 0);
+    /// ```
+    #[inline]
+    pub fn clip_depth(&self) -> usize {
+        self.clips.len()
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips.pop().is_some() {
+            self.commands.push(Command::PopLayer);
+        }
     }
 }
 
 impl Default for Renderer {
     #[inline]
     fn default() -> Self {
-        let mut scene = Box::<SceneFragment>::default();
-        let builder = {
-            let scene_mut: &mut SceneFragment = &mut scene;
-            SceneBuilder::for_fragment(unsafe {
-                // Quite a hack until we have a better way to do this in Vello.
-                // Pretend that the scene fragment pointer lives for 'static.
-                std::mem::transmute::<&mut SceneFragment, &'static mut SceneFragment>(scene_mut)
-            })
-        };
-
         Self {
-            scene,
-            builder,
+            commands: Vec::new(),
+            elements: Vec::new(),
             transforms: vec![Affine::IDENTITY],
-            clips: vec![false],
+            clips: Vec::new(),
         }
     }
 }
-
 impl renderer::Renderer for Renderer {
     type Buffer = Buffer;
 
@@ -419,19 +961,23 @@ impl renderer::Renderer for Renderer {
         let last_transform = *self.last_transform();
 
         self.transforms.push(last_transform);
-        self.clips.push(false);
     }
 
     #[inline]
     fn state_pop(&mut self) {
-        self.transforms.pop();
-        if self.clips.pop().unwrap_or_default() {
-            self.builder.pop_layer();
+        let level = self.transforms.len() - 1;
+        while self
+            .clips
+            .last()
+            .is_some_and(|&clip_level| clip_level >= level)
+        {
+            self.pop_clip();
         }
 
+        self.transforms.pop();
+
         if self.transforms.is_empty() {
             self.transforms.push(Affine::IDENTITY);
-            self.clips.push(false);
         }
     }
 
@@ -444,66 +990,122 @@ impl renderer::Renderer for Renderer {
     #[inline]
     fn set_clip(&mut self, path: &Self::Path) {
         let transform = *self.last_transform();
-
-        if *self.last_clip() {
-            self.builder.pop_layer();
+        let level = self.transforms.len() - 1;
+
+        // Clip layers are always filled with the non-zero rule. Masking an even-odd clip with a
+        // separate layer would isolate its contents from the backdrop and break blend modes.
+        match path.fill {
+            Fill::NonZero => self.push_layer(Mix::Clip, 1.0, transform, &path.inner),
+            Fill::EvenOdd => path.with_non_zero(transform, |path| {
+                self.push_layer(Mix::Clip, 1.0, transform, path);
+            }),
         }
 
-        self.builder
-            .push_layer(Mix::Clip, 1.0, transform, &path.inner);
-
-        *self.last_clip() = true;
+        self.clips.push(level);
     }
 
     #[inline]
     fn draw_path(&mut self, path: &Self::Path, paint: &Self::Paint) {
         let transform = *self.last_transform();
 
-        let builder = &mut self.builder;
-
         let skip_blending = paint.blend_mode == Mix::Normal.into();
 
         if !skip_blending {
-            builder.push_layer(paint.blend_mode, 1.0, transform, &path.inner.bounding_box());
+            // The layer must contain the whole stroke, including miter joins.
+            let bounds = match &paint.style {
+                RenderStyle::Fill => path.inner.bounding_box(),
+                RenderStyle::Stroke(stroke) => {
+                    let outset =
+                        0.5 * f64::from(stroke.width) * f64::from(stroke.miter_limit).max(1.0);
+                    path.inner.bounding_box().inflate(outset, outset)
+                }
+            };
+
+            self.push_layer(paint.blend_mode, 1.0, transform, &bounds);
         }
 
-        match &paint.style {
-            RenderStyle::Fill => {
-                builder.fill(path.fill, transform, &paint.brush, None, &path.inner)
-            }
-            RenderStyle::Stroke(stroke) => {
-                builder.stroke(stroke, transform, &paint.brush, None, &path.inner)
-            }
-        }
+        let shape = self.push_shape(&path.inner);
+
+        self.commands.push(match &paint.style {
+            RenderStyle::Fill => Command::Fill {
+                fill: path.fill,
+                transform,
+                brush: paint.brush.clone(),
+                brush_transform: None,
+                shape,
+            },
+            RenderStyle::Stroke(stroke) => Command::Stroke {
+                stroke: stroke.clone(),
+                transform,
+                brush: paint.brush.clone(),
+                shape,
+            },
+        });
 
         if !skip_blending {
-            builder.pop_layer();
+            self.commands.push(Command::PopLayer);
         }
     }
 
     #[inline]
-    fn draw_image(&mut self, image: &Self::Image, blend_mode: renderer::BlendMode, opacity: f32) {
-        let image = &image.inner;
-        let mix: Mix = to_vello_mix(blend_mode);
+    fn draw_image(
+        &mut self,
+        image: &Self::Image,
+        sampler: renderer::ImageSampler,
+        blend_mode: renderer::BlendMode,
+        opacity: f32,
+    ) {
+        let width = image.inner.width as f32;
+        let height = image.inner.height as f32;
+
+        let [chunk] = &image.sampled(sampler.filter).chunks[..] else {
+            // Images made up of several chunks are drawn like a mesh of two triangles.
+            let [left, top, right, bottom] =
+                [-0.5 * width, -0.5 * height, 0.5 * width, 0.5 * height];
+            let corners = [[left, top], [right, top], [right, bottom], [left, bottom]];
+            let uvs = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
+            self.push_image_triangles(
+                image,
+                sampler,
+                &[
+                    (
+                        [corners[0], corners[1], corners[2]],
+                        [uvs[0], uvs[1], uvs[2]],
+                    ),
+                    (
+                        [corners[0], corners[2], corners[3]],
+                        [uvs[0], uvs[2], uvs[3]],
+                    ),
+                ],
+                blend_mode,
+                opacity,
+            );
+
+            return;
+        };
 
-        let transform = self.last_transform().pre_translate(Vec2::new(
-            image.width as f64 * -0.5,
-            image.height as f64 * -0.5,
-        ));
-        let rect = Rect::new(0.0, 0.0, image.width as f64, image.height as f64);
+        let scale = chunk.image.width as f64 / image.inner.width as f64;
+        let mix: Mix = to_vello_mix(blend_mode);
 
-        let builder = &mut self.builder;
+        let transform = self
+            .last_transform()
+            .pre_translate(Vec2::new(width as f64 * -0.5, height as f64 * -0.5));
+        let rect = Rect::new(0.0, 0.0, width as f64, height as f64);
 
         let skip_blending = mix == Mix::Normal && opacity == 1.0;
 
-        if skip_blending {
-            builder.push_layer(mix, opacity, transform, &rect);
+        if !skip_blending {
+            self.push_layer(mix, opacity, transform, &rect);
         }
 
-        builder.draw_image(image, transform);
+        self.commands.push(Command::DrawImage {
+            image: chunk.image.clone(),
+            transform: transform * Affine::scale(scale.recip()),
+        });
 
-        if skip_blending {
-            builder.pop_layer();
+        if !skip_blending {
+            self.commands.push(Command::PopLayer);
         }
     }
 
@@ -511,69 +1113,438 @@ impl renderer::Renderer for Renderer {
     fn draw_image_mesh(
         &mut self,
         image: &Self::Image,
+        sampler: renderer::ImageSampler,
         vertices: &Self::Buffer,
         uvs: &Self::Buffer,
         indices: &Self::Buffer,
         blend_mode: renderer::BlendMode,
         opacity: f32,
     ) {
-        let image = &image.inner;
         let vertices = vertices.as_f32_pairs();
         let uvs = uvs.as_f32_pairs();
 
-        let mix: Mix = to_vello_mix(blend_mode);
+        let triangles: Vec<_> = indices
+            .as_u16s()
+            .chunks_exact(3)
+            .map(|triangle_indices| {
+                (
+                    [0, 1, 2].map(|i| vertices[triangle_indices[i] as usize]),
+                    [0, 1, 2].map(|i| uvs[triangle_indices[i] as usize]),
+                )
+            })
+            .collect();
 
-        for triangle_indices in indices.as_u16s().chunks_exact(3) {
-            let points = [
-                vertices[triangle_indices[0] as usize],
-                vertices[triangle_indices[1] as usize],
-                vertices[triangle_indices[2] as usize],
-            ];
-            let uvs = [
-                uvs[triangle_indices[0] as usize],
-                uvs[triangle_indices[1] as usize],
-                uvs[triangle_indices[2] as usize],
-            ];
-
-            let center = Point::new(
-                ((points[0][0] + points[1][0] + points[2][0]) / 3.0) as f64,
-                ((points[0][1] + points[1][1] + points[2][1]) / 3.0) as f64,
-            );
+        self.push_image_triangles(image, sampler, &triangles, blend_mode, opacity);
+    }
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderer")
+            .field("commands", &self.commands.len())
+            .field("transforms", &self.transforms)
+            .field("clips", &self.clips)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        path::FillRule,
+        renderer::{Paint as _, Path as _, Renderer as _},
+    };
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Op {
+        PushLayer(BlendMode),
+        PopLayer,
+        Fill(Fill),
+        Stroke,
+        DrawImage,
+    }
 
-            let path = triangle_path(points.map(|v| Point::new(v[0] as f64, v[1] as f64)));
+    fn ops(renderer: &Renderer) -> Vec<Op> {
+        renderer
+            .commands
+            .iter()
+            .map(|command| match command {
+                Command::PushLayer { blend_mode, .. } => Op::PushLayer(*blend_mode),
+                Command::PopLayer => Op::PopLayer,
+                Command::Fill { fill, .. } => Op::Fill(*fill),
+                Command::Stroke { .. } => Op::Stroke,
+                Command::DrawImage { .. } => Op::DrawImage,
+            })
+            .collect()
+    }
 
-            let transform = self.last_transform().pre_scale_from_origin(1.03, center);
-            let brush_transform =
-                util::map_uvs_to_triangle(&points, &uvs, image.width, image.height);
+    fn triangle(fill_rule: FillRule) -> Path {
+        let mut path = Path::default();
+        path.move_to(0.0, 0.0);
+        path.line_to(10.0, 0.0);
+        path.line_to(0.0, 10.0);
+        path.close();
+        path.set_fill_rule(fill_rule);
 
-            let builder = &mut self.builder;
+        path
+    }
 
-            let skip_blending = mix == Mix::Normal;
+    fn paint(blend_mode: renderer::BlendMode) -> Paint {
+        let mut paint = Paint::default();
+        paint.set_blend_mode(blend_mode);
 
-            if !skip_blending {
-                builder.push_layer(mix, opacity, transform, &path.bounding_box());
-            }
+        paint
+    }
 
-            builder.fill(
-                Fill::NonZero,
-                transform,
-                BrushRef::Image(image),
-                Some(brush_transform),
-                &path,
-            );
+    #[test]
+    fn nested_clips() {
+        let mut renderer = Renderer::default();
 
-            if !skip_blending {
-                builder.pop_layer();
-            }
+        renderer.state_push();
+        renderer.set_clip(&triangle(FillRule::NonZero));
+
+        renderer.state_push();
+        renderer.set_clip(&triangle(FillRule::EvenOdd));
+        renderer.draw_path(
+            &triangle(FillRule::NonZero),
+            &paint(renderer::BlendMode::Multiply),
+        );
+        renderer.state_pop();
+
+        renderer.draw_path(
+            &triangle(FillRule::EvenOdd),
+            &paint(renderer::BlendMode::SrcOver),
+        );
+        renderer.state_pop();
+
+        assert_eq!(
+            ops(&renderer),
+            [
+                Op::PushLayer(Mix::Clip.into()),
+                Op::PushLayer(Mix::Clip.into()),
+                Op::PushLayer(Mix::Multiply.into()),
+                Op::Fill(Fill::NonZero),
+                Op::PopLayer,
+                Op::PopLayer,
+                Op::Fill(Fill::EvenOdd),
+                Op::PopLayer,
+            ]
+        );
+        assert_eq!(renderer.clip_depth(), 0);
+    }
+
+    #[test]
+    fn clips_intersect_until_state_pop() {
+        let mut renderer = Renderer::default();
+
+        renderer.state_push();
+        renderer.set_clip(&triangle(FillRule::EvenOdd));
+        renderer.set_clip(&triangle(FillRule::NonZero));
+        renderer.draw_path(
+            &triangle(FillRule::NonZero),
+            &paint(renderer::BlendMode::Screen),
+        );
+        assert_eq!(renderer.clip_depth(), 2);
+        renderer.state_pop();
+
+        renderer.draw_path(
+            &triangle(FillRule::NonZero),
+            &paint(renderer::BlendMode::SrcOver),
+        );
+
+        assert_eq!(
+            ops(&renderer),
+            [
+                Op::PushLayer(Mix::Clip.into()),
+                Op::PushLayer(Mix::Clip.into()),
+                Op::PushLayer(Mix::Screen.into()),
+                Op::Fill(Fill::NonZero),
+                Op::PopLayer,
+                Op::PopLayer,
+                Op::PopLayer,
+                Op::Fill(Fill::NonZero),
+            ]
+        );
+    }
+
+    #[test]
+    fn even_odd_clips_are_not_isolated() {
+        let mut renderer = Renderer::default();
+
+        renderer.state_push();
+        renderer.set_clip(&triangle(FillRule::EvenOdd));
+
+        let Command::PushLayer {
+            blend_mode, shape, ..
+        } = &renderer.commands[0]
+        else {
+            panic!("clip did not push a layer");
+        };
+
+        // Only clip layers let blend modes inside of them reach the backdrop.
+        assert_eq!(blend_mode.mix, Mix::Clip);
+        assert_eq!(
+            BezPath::from_vec(renderer.elements[shape.clone()].to_vec())
+                .area()
+                .abs(),
+            50.0,
+        );
+
+        renderer.state_pop();
+        assert_eq!(renderer.commands.len(), 2);
+    }
+
+    fn clip_len(renderer: &Renderer) -> usize {
+        match renderer.commands.last() {
+            Some(Command::PushLayer { shape, .. }) => shape.len(),
+            _ => panic!("last command is not a clip layer"),
         }
     }
-}
 
-impl fmt::Debug for Renderer {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Renderer")
-            .field("transforms", &self.transforms)
-            .field("clips", &self.clips)
-            .finish()
+    #[test]
+    fn even_odd_clips_are_flattened_for_their_scale() {
+        let mut circle = Path::default();
+        circle.move_to(0.0, 10.0);
+        circle.cubic_to(0.0, 4.5, 4.5, 0.0, 10.0, 0.0);
+        circle.cubic_to(15.5, 0.0, 20.0, 4.5, 20.0, 10.0);
+        circle.cubic_to(20.0, 15.5, 15.5, 20.0, 10.0, 20.0);
+        circle.cubic_to(4.5, 20.0, 0.0, 15.5, 0.0, 10.0);
+        circle.close();
+        circle.set_fill_rule(FillRule::EvenOdd);
+
+        let mut renderer = Renderer::default();
+
+        renderer.set_clip(&circle);
+        let len = clip_len(&renderer);
+        let tolerance = circle.non_zero.lock().unwrap().as_ref().unwrap().0;
+
+        // The converted path is reused for transforms of a similar scale.
+        renderer.transform(&[1.5, 0.0, 0.0, 1.5, 100.0, 0.0]);
+        renderer.set_clip(&circle);
+        assert_eq!(clip_len(&renderer), len);
+        assert_eq!(
+            circle.non_zero.lock().unwrap().as_ref().unwrap().0,
+            tolerance
+        );
+
+        renderer.transform(&[100.0, 0.0, 0.0, 100.0, 0.0, 0.0]);
+        renderer.set_clip(&circle);
+        assert!(clip_len(&renderer) > len);
+        assert!(circle.non_zero.lock().unwrap().as_ref().unwrap().0 < tolerance / 100.0);
+
+        circle.move_to(0.0, 0.0);
+        assert!(circle.non_zero.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn reset_clears_clips() {
+        let mut renderer = Renderer::default();
+
+        renderer.state_push();
+        renderer.set_clip(&triangle(FillRule::NonZero));
+        renderer.reset();
+
+        assert_eq!(renderer.clip_depth(), 0);
+        assert!(ops(&renderer).is_empty());
+    }
+
+    fn image() -> Image {
+        Image::new(peniko::Image::new(
+            vec![255; 4 * 4].into(),
+            Format::Rgba8,
+            2,
+            2,
+        ))
+    }
+
+    fn buffer<T: bytemuck::Pod>(data: &[T]) -> Buffer {
+        Buffer {
+            inner: bytemuck::cast_slice(data).to_vec(),
+        }
+    }
+
+    fn draw_mesh(renderer: &mut Renderer, vertices: &[[f32; 2]], indices: &[u16], opacity: f32) {
+        let uvs: Vec<_> = vertices.iter().map(|v| v.map(|c| c / 10.0)).collect();
+
+        renderer.draw_image_mesh(
+            &image(),
+            renderer::ImageSampler::default(),
+            &buffer(vertices),
+            &buffer(&uvs),
+            &buffer(indices),
+            renderer::BlendMode::SrcOver,
+            opacity,
+        );
+    }
+
+    fn fill_shapes(renderer: &Renderer) -> Vec<BezPath> {
+        renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::Fill { shape, .. } => {
+                    Some(BezPath::from_vec(renderer.elements[shape.clone()].to_vec()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_triangles_are_drawn_in_order() {
+        let mut renderer = Renderer::default();
+
+        draw_mesh(
+            &mut renderer,
+            &[
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [0.0, 10.0],
+                [2.0, 2.0],
+                [20.0, 2.0],
+                [2.0, 20.0],
+            ],
+            &[3, 4, 5, 0, 1, 2],
+            1.0,
+        );
+
+        assert_eq!(
+            ops(&renderer),
+            [Op::Fill(Fill::NonZero), Op::Fill(Fill::NonZero)]
+        );
+
+        let bounds: Vec<_> = fill_shapes(&renderer)
+            .iter()
+            .map(|shape| shape.bounding_box())
+            .collect();
+        assert_eq!(
+            bounds,
+            [
+                Rect::new(2.0, 2.0, 20.0, 20.0),
+                Rect::new(0.0, 0.0, 10.0, 10.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn shared_edges_are_outset_below_later_triangles() {
+        let mut renderer = Renderer::default();
+
+        draw_mesh(
+            &mut renderer,
+            &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            &[0, 1, 2, 0, 2, 3],
+            1.0,
+        );
+
+        let shapes = fill_shapes(&renderer);
+
+        assert_eq!(shapes[0].area().abs(), 50.0 + 10.0 * 2.0f64.sqrt());
+        assert_eq!(shapes[1].area().abs(), 50.0);
+    }
+
+    #[test]
+    fn transparent_meshes_are_blended_at_once() {
+        let mut renderer = Renderer::default();
+
+        draw_mesh(
+            &mut renderer,
+            &[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            &[0, 1, 2, 0, 2, 3],
+            0.5,
+        );
+
+        assert_eq!(
+            ops(&renderer),
+            [
+                Op::PushLayer(Mix::Normal.into()),
+                Op::Fill(Fill::NonZero),
+                Op::Fill(Fill::NonZero),
+                Op::PopLayer,
+            ]
+        );
+    }
+
+    fn brush_transforms(renderer: &Renderer) -> Vec<Affine> {
+        renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::Fill {
+                    brush_transform, ..
+                } => *brush_transform,
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wrapped_meshes_are_drawn_one_repetition_at_a_time() {
+        let mut renderer = Renderer::default();
+
+        renderer.draw_image_mesh(
+            &image(),
+            renderer::ImageSampler {
+                wrap_x: renderer::ImageWrap::Mirror,
+                wrap_y: renderer::ImageWrap::Clamp,
+                filter: renderer::ImageFilter::Bilinear,
+            },
+            &buffer(&[[0.0f32, 0.0], [20.0, 0.0], [0.0, 10.0]]),
+            &buffer(&[[0.0f32, 0.0], [2.0, 0.0], [0.0, 1.0]]),
+            &buffer(&[0u16, 1, 2]),
+            renderer::BlendMode::SrcOver,
+            1.0,
+        );
+
+        let areas: Vec<_> = fill_shapes(&renderer)
+            .iter()
+            .map(|shape| shape.area().abs())
+            .collect();
+        let transforms = brush_transforms(&renderer);
+
+        // The first repetition is drawn below the second one and outset along their border.
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas[0], 75.0 + 5.0);
+        assert_eq!(areas[1], 25.0);
+
+        // Pixels of the 2x2 image are 5 units wide. The second repetition is mirrored.
+        assert_eq!(transforms[0] * Point::new(1.0, 0.0), Point::new(5.0, 0.0));
+        assert_eq!(transforms[1] * Point::new(1.0, 0.0), Point::new(15.0, 0.0));
+    }
+
+    #[test]
+    fn large_nearest_images_are_chunked() {
+        let width = 3000u32;
+        let data: Vec<u8> = (0..width).flat_map(|x| x.to_le_bytes()).collect();
+        let image = Image::new(peniko::Image::new(data.into(), Format::Rgba8, width, 1));
+
+        let sampled = image.sampled(renderer::ImageFilter::Nearest);
+
+        assert_eq!(sampled.scale, 8);
+        assert_eq!(sampled.chunk_size, 512);
+        assert_eq!(sampled.columns, 6);
+        assert_eq!(sampled.chunks.len(), 6);
+
+        let chunk = &sampled.chunks[5];
+        assert_eq!(chunk.origin, [2560, 0]);
+        assert_eq!((chunk.image.width, chunk.image.height), (440 * 8, 8));
+        assert_eq!(chunk.image.data.data()[..4], 2560u32.to_le_bytes());
+
+        let mut renderer = Renderer::default();
+        renderer.draw_image(
+            &image,
+            renderer::ImageSampler {
+                filter: renderer::ImageFilter::Nearest,
+                ..Default::default()
+            },
+            renderer::BlendMode::SrcOver,
+            1.0,
+        );
+
+        let ops = ops(&renderer);
+        assert_eq!(ops.len(), 12);
+        assert!(ops.iter().all(|op| *op == Op::Fill(Fill::NonZero)));
     }
 }
@@ -1,94 +1,252 @@
-use std::ops::{Add, Mul, Sub};
+use vello::kurbo::{self, Affine, BezPath, PathEl, Point, Vec2};
 
-use vello::kurbo::{Affine, Point};
+pub fn map_uvs_to_triangle(
+    points: &[[f32; 2]; 3],
+    uvs: &[[f32; 2]; 3],
+    width: u32,
+    height: u32,
+) -> Affine {
+    Affine::new(crate::util::map_uvs_to_triangle(points, uvs, width, height).map(f64::from))
+}
 
+/// Line of a flattened path, going downwards from `start` to `end`.
 #[derive(Clone, Copy, Debug)]
-struct Vec2 {
-    x: f32,
-    y: f32,
+struct Edge {
+    start: Point,
+    end: Point,
 }
 
-impl Add for Vec2 {
-    type Output = Self;
+impl Edge {
+    fn new(a: Point, b: Point) -> Option<Self> {
+        match a.y.partial_cmp(&b.y)? {
+            std::cmp::Ordering::Less => Some(Self { start: a, end: b }),
+            std::cmp::Ordering::Greater => Some(Self { start: b, end: a }),
+            // Horizontal edges don't change the winding of any point.
+            std::cmp::Ordering::Equal => None,
+        }
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    fn x_at(&self, y: f64) -> f64 {
+        if y == self.start.y {
+            self.start.x
+        } else if y == self.end.y {
+            self.end.x
+        } else {
+            self.start.x
+                + (y - self.start.y) * (self.end.x - self.start.x) / (self.end.y - self.start.y)
         }
     }
-}
 
-impl Sub for Vec2 {
-    type Output = Self;
+    fn intersection_y(&self, other: &Self) -> Option<f64> {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let denom = r.cross(s);
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+        if denom == 0.0 {
+            return None;
         }
+
+        let offset = other.start - self.start;
+        let t = offset.cross(s) / denom;
+        let u = offset.cross(r) / denom;
+
+        (t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0).then_some(self.start.y + t * r.y)
     }
 }
 
-impl Mul<f32> for Vec2 {
-    type Output = Self;
+/// Converts `path` filled with the even-odd rule into a path that covers the same area when
+/// filled with the non-zero rule.
+///
+/// The flattened path is cut into horizontal bands at all of its vertices and intersections, so
+/// that no edges cross inside of a band. Every other span between the edges of a band is inside
+/// of the path and becomes a trapezoid. All trapezoids wind the same way and neighbors share
+/// their edges exactly, so the result is filled without seams.
+pub fn even_odd_to_non_zero(path: &BezPath, tolerance: f64) -> BezPath {
+    let mut edges = Vec::new();
+    let mut start = Point::ZERO;
+    let mut last = Point::ZERO;
+
+    kurbo::flatten(path.elements().iter().copied(), tolerance, |el| match el {
+        PathEl::MoveTo(p) => {
+            edges.extend(Edge::new(last, start));
+            start = p;
+            last = p;
+        }
+        PathEl::LineTo(p) => {
+            edges.extend(Edge::new(last, p));
+            last = p;
+        }
+        PathEl::ClosePath => {
+            edges.extend(Edge::new(last, start));
+            last = start;
+        }
+        _ => (),
+    });
+    edges.extend(Edge::new(last, start));
+
+    edges.sort_by(|a, b| a.start.y.total_cmp(&b.start.y));
+
+    let mut ys: Vec<f64> = edges.iter().flat_map(|e| [e.start.y, e.end.y]).collect();
+    for (i, edge) in edges.iter().enumerate() {
+        for other in edges[i + 1..]
+            .iter()
+            .take_while(|other| other.start.y < edge.end.y)
+        {
+            ys.extend(edge.intersection_y(other));
+        }
+    }
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+
+    let mut result = BezPath::new();
+    let mut spans = Vec::new();
+
+    for band in ys.windows(2) {
+        let (top, bottom) = (band[0], band[1]);
+
+        spans.clear();
+        spans.extend(
+            edges
+                .iter()
+                .take_while(|edge| edge.start.y < bottom)
+                .filter(|edge| edge.end.y > top)
+                .map(|edge| (edge.x_at(top), edge.x_at(bottom))),
+        );
+        spans.sort_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)));
+
+        for pair in spans.chunks_exact(2) {
+            let [(left_top, left_bottom), (right_top, right_bottom)] = [pair[0], pair[1]];
+
+            result.move_to((left_top, top));
+            result.line_to((right_top, top));
+            result.line_to((right_bottom, bottom));
+            result.line_to((left_bottom, bottom));
+            result.close_path();
         }
     }
+
+    result
 }
 
-/// Finds the affine transform that maps triangle `from` to triangle `to`. The algorithm is based
-/// on the [Simplex Affine Mapping] method which has a [Swift implementation].
+/// Returns the outline of the convex polygon `points` where every edge flagged in `outset` is
+/// pushed outwards by `offset`.
 ///
-/// [Simplex Affine Mapping]: https://www.researchgate.net/publication/332410209_Beginner%27s_guide_to_mapping_simplexes_affinely
-/// [Swift implementation]: https://rethunk.medium.com/finding-an-affine-transform-using-three-2d-point-correspondences-using-simplex-affine-mapping-255aeb4e8055
-fn simplex_affine_mapping(from: [Vec2; 3], to: [Vec2; 3]) -> Affine {
-    let [a, b, c] = from;
-    let [d, e, f] = to;
-
-    let det_recip = (a.x * b.y + b.x * c.y + c.x * a.y - a.x * c.y - b.x * a.y - c.x * b.y).recip();
+/// Neighbors that share an edge are anti-aliased along it, so that the pixels on the edge would
+/// only be partially covered by either of them. Drawn below its neighbor, a polygon that is
+/// outset along the edge fills these pixels, so the two meet without a seam.
+pub fn outset_polygon(points: &[Point], outset: &[bool], offset: f64) -> Vec<PathEl> {
+    let centroid = points
+        .iter()
+        .fold(Point::ZERO, |sum, p| sum + p.to_vec2())
+        .to_vec2()
+        / points.len() as f64;
+
+    let mut elements = Vec::with_capacity(points.len() * 3 + 1);
+
+    for (i, (&p, &outset)) in points.iter().zip(outset).enumerate() {
+        elements.push(if i == 0 {
+            PathEl::MoveTo(p)
+        } else {
+            PathEl::LineTo(p)
+        });
+
+        let q = points[(i + 1) % points.len()];
+        let edge = q - p;
+
+        if !outset || edge.hypot() == 0.0 {
+            continue;
+        }
 
-    let p = (d * (b.y - c.y) - e * (a.y - c.y) + f * (a.y - b.y)) * det_recip;
+        let mut normal = Vec2::new(edge.y, -edge.x) / edge.hypot();
+        if normal.dot(p.midpoint(q).to_vec2() - centroid) < 0.0 {
+            normal = -normal;
+        }
 
-    let q = (e * (a.x - c.x) - d * (b.x - c.x) - f * (a.x - b.x)) * det_recip;
+        elements.push(PathEl::LineTo(p + normal * offset));
+        elements.push(PathEl::LineTo(q + normal * offset));
+    }
 
-    let t = (d * (b.x * c.y - b.y * c.x) - e * (a.x * c.y - a.y * c.x)
-        + f * (a.x * b.y - a.y * b.x))
-        * det_recip;
+    elements.push(PathEl::ClosePath);
 
-    Affine::new([
-        p.x as f64, p.y as f64, q.x as f64, q.y as f64, t.x as f64, t.y as f64,
-    ])
+    elements
 }
 
-pub fn map_uvs_to_triangle(
-    points: &[[f32; 2]; 3],
-    uvs: &[[f32; 2]; 3],
-    width: u32,
-    height: u32,
-) -> Affine {
-    simplex_affine_mapping(
-        uvs.map(|v| Vec2 {
-            x: v[0] * width as f32,
-            y: v[1] * height as f32,
-        }),
-        points.map(|v| Vec2 { x: v[0], y: v[1] }),
-    )
-}
+#[cfg(test)]
+mod tests {
+    use vello::kurbo::{Rect, Shape};
+
+    use super::*;
+
+    fn even_odd_area(path: &BezPath) -> f64 {
+        let path = even_odd_to_non_zero(path, 0.1);
+
+        // All trapezoids must wind the same way for the non-zero rule to fill each of them.
+        let areas: Vec<f64> = path
+            .elements()
+            .split(|el| matches!(el, PathEl::ClosePath))
+            .filter(|elements| !elements.is_empty())
+            .map(|elements| {
+                let mut trapezoid = BezPath::from_vec(elements.to_vec());
+                trapezoid.close_path();
+                trapezoid.area()
+            })
+            .collect();
+        assert!(areas.iter().all(|&area| area >= 0.0) || areas.iter().all(|&area| area <= 0.0));
+
+        areas.iter().sum::<f64>().abs()
+    }
 
-pub trait ScaleFromOrigin {
-    fn pre_scale_from_origin(self, scale: f64, origin: Point) -> Self;
-}
+    #[test]
+    fn hole_with_same_winding() {
+        let mut path = Rect::new(0.0, 0.0, 10.0, 10.0).to_path(0.1);
+        path.extend(Rect::new(2.0, 2.0, 8.0, 8.0).to_path(0.1));
+
+        assert_eq!(even_odd_area(&path), 64.0);
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let mut path = Rect::new(0.0, 0.0, 2.0, 2.0).to_path(0.1);
+        path.extend(Rect::new(1.0, 1.0, 3.0, 3.0).to_path(0.1));
+
+        assert_eq!(even_odd_area(&path), 6.0);
+    }
+
+    #[test]
+    fn self_intersecting_bow_tie() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((4.0, 4.0));
+        path.line_to((4.0, 0.0));
+        path.line_to((0.0, 4.0));
+        path.close_path();
+
+        assert_eq!(even_odd_area(&path), 8.0);
+    }
+
+    #[test]
+    fn unclosed_subpath() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((4.0, 0.0));
+        path.line_to((4.0, 4.0));
+
+        assert_eq!(even_odd_area(&path), 8.0);
+    }
+
+    #[test]
+    fn outset_edges_only() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+
+        let path = BezPath::from_vec(outset_polygon(&square, &[false, true, false, false], 0.5));
 
-impl ScaleFromOrigin for Affine {
-    fn pre_scale_from_origin(self, scale: f64, origin: Point) -> Self {
-        let origin = origin.to_vec2();
-        self.pre_translate(origin)
-            .pre_scale(scale)
-            .pre_translate(-origin)
+        assert_eq!(path.area().abs(), 5.0);
+        assert_eq!(path.bounding_box(), Rect::new(0.0, 0.0, 2.5, 2.0));
     }
 }
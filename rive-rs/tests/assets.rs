@@ -0,0 +1,153 @@
+#![cfg(all(feature = "software", not(feature = "vello")))]
+
+use std::{io::Cursor, time::Duration};
+
+use rive_rs::{
+    assets::{AssetType, ImageAsset},
+    software::Renderer,
+    Handle, Instantiate,
+};
+
+type File = rive_rs::File<Renderer>;
+type Artboard = rive_rs::Artboard<Renderer>;
+
+const ASSET_ID: u32 = 42;
+const SIZE: u32 = 10;
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+fn png(color: [u8; 4]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image::RgbaImage::from_pixel(2, 2, image::Rgba(color))
+        .write_to(&mut Cursor::new(&mut bytes), image::ImageOutputFormat::Png)
+        .unwrap();
+
+    bytes
+}
+
+fn uint(mut value: u64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+fn bytes(value: &[u8]) -> Vec<u8> {
+    let mut bytes = uint(value.len() as u64);
+    bytes.extend_from_slice(value);
+
+    bytes
+}
+
+fn float(value: f32) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+fn object(type_key: u64, properties: &[(u64, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = uint(type_key);
+    for (key, value) in properties {
+        bytes.extend(uint(*key));
+        bytes.extend_from_slice(value);
+    }
+    bytes.extend(uint(0));
+
+    bytes
+}
+
+/// Writes a minimal Rive file with an image asset, embedding `image` if there is one, and an
+/// artboard that is covered by the image.
+fn riv(image: Option<&[u8]>) -> Vec<u8> {
+    let mut riv = b"RIVE".to_vec();
+    // Major and minor version, file ID and an empty table of contents.
+    for value in [7, 0, 0, 0] {
+        riv.extend(uint(value));
+    }
+
+    // Backboard.
+    riv.extend(object(23, &[]));
+    // Image asset with its name and identifier.
+    riv.extend(object(
+        105,
+        &[(203, bytes(b"image")), (204, uint(ASSET_ID as u64))],
+    ));
+    if let Some(image) = image {
+        // Contents of the image asset.
+        riv.extend(object(106, &[(212, bytes(image))]));
+    }
+    // Artboard with its name, width and height.
+    riv.extend(object(
+        1,
+        &[
+            (4, bytes(b"artboard")),
+            (7, float(SIZE as f32)),
+            (8, float(SIZE as f32)),
+        ],
+    ));
+    // Image with the artboard as its parent, centered on the artboard and scaled to cover it,
+    // showing the first asset of the file.
+    let center = SIZE as f32 / 2.0;
+    riv.extend(object(
+        100,
+        &[
+            (5, uint(0)),
+            (13, float(center)),
+            (14, float(center)),
+            (16, float(center)),
+            (17, float(center)),
+            (206, uint(0)),
+        ],
+    ));
+
+    riv
+}
+
+fn center_color(artboard: &mut Artboard) -> [u8; 4] {
+    let mut renderer = Renderer::new(SIZE, SIZE).unwrap();
+
+    artboard.advance(Duration::ZERO);
+    artboard.draw(&mut renderer);
+
+    let pixel = renderer.pixmap().pixel(SIZE / 2, SIZE / 2).unwrap();
+    [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+}
+
+#[test]
+fn swapped_images_are_drawn_by_existing_artboards() {
+    let mut file = File::new(&riv(Some(&png(RED)))).unwrap();
+    let mut artboard = Artboard::instantiate(&file, Handle::Default).unwrap();
+
+    assert_eq!(center_color(&mut artboard), RED);
+
+    let asset = file
+        .assets()
+        .find(|asset| asset.r#type() == AssetType::Image)
+        .unwrap();
+    assert!(ImageAsset::try_from(asset).unwrap().set_image(&png(BLUE)));
+
+    assert_eq!(center_color(&mut artboard), BLUE);
+}
+
+#[test]
+fn assets_load_after_artboards_are_instantiated() {
+    let mut file = File::builder()
+        .asset_loader(|asset: &mut rive_rs::assets::Asset<'_>| asset.id() == ASSET_ID)
+        .build(&riv(None))
+        .unwrap();
+    let mut artboard = Artboard::instantiate(&file, Handle::Default).unwrap();
+
+    assert_eq!(center_color(&mut artboard)[3], 0);
+
+    assert!(file.load_asset(ASSET_ID, &png(BLUE)));
+
+    assert_eq!(center_color(&mut artboard), BLUE);
+}
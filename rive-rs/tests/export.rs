@@ -0,0 +1,61 @@
+#![cfg(feature = "export")]
+
+use rive_rs::{
+    export::{Exporter, File, Options},
+    Handle,
+};
+
+fn exporter(fps: u32) -> Exporter {
+    let file = File::new(include_bytes!("../../assets/rating-animation.riv")).unwrap();
+    let options = Options {
+        width: 64,
+        height: 48,
+        fps,
+        ..Options::default()
+    };
+
+    Exporter::new(&file, Handle::Default, Handle::Default, &options).unwrap()
+}
+
+#[test]
+fn frame_count_scales_with_fps() {
+    let frames = exporter(30).frame_count();
+    let double_frames = exporter(60).frame_count();
+
+    assert!(frames > 0);
+    // Depending on the loop mode, the last frame is either included or not.
+    assert!((2 * frames - 2..=2 * frames).contains(&double_frames));
+}
+
+#[test]
+fn png_round_trip() {
+    let mut exporter = exporter(30);
+
+    let mut bytes = Vec::new();
+    exporter.write_png(0, &mut bytes).unwrap();
+
+    let mut reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).unwrap();
+
+    assert_eq!((info.width, info.height), (64, 48));
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(info.bit_depth, png::BitDepth::Eight);
+    assert_eq!(data, exporter.render_frame(0).to_rgba8());
+}
+
+#[test]
+fn apng_contains_every_frame() {
+    let mut exporter = exporter(30);
+
+    let mut bytes = Vec::new();
+    exporter.write_apng(&mut bytes).unwrap();
+
+    let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+    let animation_control = reader.info().animation_control().unwrap();
+
+    assert_eq!(
+        animation_control.num_frames as usize,
+        exporter.frame_count()
+    );
+}